@@ -0,0 +1,9 @@
+pub mod video;
+pub mod text;
+pub mod text_screen;
+pub mod keyboard;
+pub mod timer;
+pub mod serial;
+pub mod printer;
+pub mod disk;
+pub mod rtc;
@@ -0,0 +1,178 @@
+//! BIOS disk services (INT 13h), reading and writing raw sectors by cylinder, head and sector.
+//! Drives are numbered from 00h for floppies and from 80h for hard disks.
+//!
+//! This bypasses DOS and the file system entirely: writing a wrong sector can destroy a disk.
+
+use core::arch::asm;
+use crate::dos::far_ptr::FarPtr;
+
+/// Size of a sector, in bytes.
+pub const SECTOR_SIZE: usize = 512;
+
+/// Error returned in AH by the BIOS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiskError {
+    InvalidCommand,
+    AddressMarkNotFound,
+    WriteProtected,
+    SectorNotFound,
+    ResetFailed,
+    DiskChanged,
+    DmaOverrun,
+    /// The buffer crosses a 64 KB boundary
+    DmaBoundary,
+    BadSector,
+    UncorrectableCrc,
+    ControllerFailure,
+    SeekFailed,
+    /// No disk in the drive, or the drive doesn't answer
+    Timeout,
+    DriveNotReady,
+    WriteFault,
+    Other(u8),
+    /// The buffer is smaller than the sectors to transfer, nothing was sent to the BIOS
+    BufferTooSmall,
+}
+
+impl From<u8> for DiskError {
+    fn from(status: u8) -> Self {
+        match status {
+            0x01 => DiskError::InvalidCommand,
+            0x02 => DiskError::AddressMarkNotFound,
+            0x03 => DiskError::WriteProtected,
+            0x04 => DiskError::SectorNotFound,
+            0x05 => DiskError::ResetFailed,
+            0x06 => DiskError::DiskChanged,
+            0x08 => DiskError::DmaOverrun,
+            0x09 => DiskError::DmaBoundary,
+            0x0A => DiskError::BadSector,
+            0x10 => DiskError::UncorrectableCrc,
+            0x20 => DiskError::ControllerFailure,
+            0x40 => DiskError::SeekFailed,
+            0x80 => DiskError::Timeout,
+            0xAA => DiskError::DriveNotReady,
+            0xCC => DiskError::WriteFault,
+            _ => DiskError::Other(status),
+        }
+    }
+}
+
+/// Address of a sector. Sectors are numbered from 1, cylinders and heads from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chs {
+    /// 10 bits
+    pub cylinder: u16,
+    pub head: u8,
+    /// 6 bits
+    pub sector: u8,
+}
+
+impl Chs {
+    pub const fn new(cylinder: u16, head: u8, sector: u8) -> Self {
+        Chs { cylinder, head, sector }
+    }
+
+    /// Cylinder and sector as packed in CX: CH is the low byte of the cylinder,
+    /// CL has its 2 high bits in bits 6-7 and the sector in bits 0-5.
+    pub const fn to_cx(&self) -> u16 {
+        (self.cylinder & 0xFF) << 8 | (self.cylinder >> 2) & 0xC0 | (self.sector & 0x3F) as u16
+    }
+
+    pub const fn from_cx(cx: u16, head: u8) -> Self {
+        Chs::new(cx >> 8 | (cx & 0xC0) << 2, head, (cx & 0x3F) as u8)
+    }
+}
+
+/// Geometry of a drive, as returned by [get_drive_params].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DriveGeometry {
+    pub cylinders: u16,
+    pub heads: u16,
+    pub sectors_per_track: u8,
+    /// Number of drives of the same kind (floppies or hard disks)
+    pub drive_count: u8,
+}
+
+/// Resets the disk controller of a drive (INT 13h AH=00h), as advised after a failed read before retrying.
+pub fn reset(drive: u8) -> Result<(), DiskError> {
+    let status: u16;
+    let carry: u16;
+    unsafe {
+        asm!("int 0x13", "setc dl",
+             inout("ax") 0x0000_u16 => status,
+             inout("dx") drive as u16 => carry);
+    }
+    if carry as u8 != 0 {
+        return Err(DiskError::from((status >> 8) as u8));
+    }
+    Ok(())
+}
+
+fn sector_function(function: u8, drive: u8, chs: Chs, count: u8, buffer: FarPtr) -> Result<u8, DiskError> {
+    let status_or_count: u16;
+    let carry: u16;
+    unsafe {
+        asm!("push es", "mov es, {segment:x}", "int 0x13", "setc dl", "pop es",
+             segment = in(reg) buffer.segment,
+             inout("ax") (function as u16) << 8 | count as u16 => status_or_count,
+             in("bx") buffer.offset,
+             in("cx") chs.to_cx(),
+             inout("dx") (chs.head as u16) << 8 | drive as u16 => carry);
+    }
+    if carry as u8 != 0 {
+        return Err(DiskError::from((status_or_count >> 8) as u8));
+    }
+    Ok(status_or_count as u8)
+}
+
+/// Reads `count` sectors starting at `chs`, within a track, into `buffer` (INT 13h AH=02h).
+/// Returns the number of sectors read.
+pub fn read_sectors(drive: u8, chs: Chs, count: u8, buffer: &mut [u8]) -> Result<u8, DiskError> {
+    if buffer.len() < count as usize * SECTOR_SIZE {
+        return Err(DiskError::BufferTooSmall);
+    }
+    sector_function(0x02, drive, chs, count, FarPtr::from_ptr(buffer.as_mut_ptr()))
+}
+
+/// Writes `count` sectors starting at `chs`, within a track, from `buffer` (INT 13h AH=03h).
+/// Returns the number of sectors written.
+///
+/// # Safety
+///
+/// **This overwrites the disk below DOS.** Writing the wrong sector, such as the boot sector, the FAT
+/// or a directory of a mounted drive, corrupts the file system and loses data, and DOS won't notice
+/// until it is too late: its buffers still hold the old content. Only write to a disk the program
+/// fully owns, and double check the drive number.
+pub unsafe fn write_sectors(drive: u8, chs: Chs, count: u8, buffer: &[u8]) -> Result<u8, DiskError> {
+    if buffer.len() < count as usize * SECTOR_SIZE {
+        return Err(DiskError::BufferTooSmall);
+    }
+    sector_function(0x03, drive, chs, count, FarPtr::from_ptr(buffer.as_ptr()))
+}
+
+/// Gets the geometry of a drive (INT 13h AH=08h).
+pub fn get_drive_params(drive: u8) -> Result<DriveGeometry, DiskError> {
+    let status: u16;
+    let carry: u16;
+    let cylinder_and_sector: u16;
+    let heads_and_count: u16;
+    unsafe {
+        // ES:DI receives the floppy parameter table
+        asm!("push es", "int 0x13", "setc bl", "pop es",
+             inout("ax") 0x0800_u16 => status,
+             out("bx") carry,
+             out("cx") cylinder_and_sector,
+             inout("dx") drive as u16 => heads_and_count,
+             out("di") _);
+    }
+    if carry as u8 != 0 {
+        return Err(DiskError::from((status >> 8) as u8));
+    }
+    let last = Chs::from_cx(cylinder_and_sector, (heads_and_count >> 8) as u8);
+    Ok(DriveGeometry {
+        cylinders: last.cylinder + 1,
+        heads: last.head as u16 + 1,
+        sectors_per_track: last.sector,
+        drive_count: heads_and_count as u8,
+    })
+}
@@ -0,0 +1,192 @@
+//! BIOS keyboard services (INT 16h), for raw, non-blocking key polling.
+
+use core::arch::asm;
+use core::ops::BitOr;
+
+/// A key read from the keyboard buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPress {
+    pub scancode: u8,
+    /// 0 for keys without an ASCII code, such as arrows and function keys.
+    pub ascii: u8,
+}
+
+impl KeyPress {
+    fn from_ax(ax: u16) -> Self {
+        KeyPress {
+            scancode: (ax >> 8) as u8,
+            ascii: ax as u8,
+        }
+    }
+
+    /// Decodes the key, from its ASCII code or, for the keys without one, its scancode.
+    pub fn key(&self) -> Key {
+        // Enhanced keyboards give E0h for the grey navigation keys
+        let is_extended = self.ascii == 0 || (self.ascii == 0xE0 && self.scancode != 0);
+        if !is_extended {
+            return match self.ascii {
+                0x08 => Key::Backspace,
+                0x09 => Key::Tab,
+                0x0D => Key::Enter,
+                0x1B => Key::Escape,
+                ascii => Key::Char(ascii),
+            };
+        }
+        match self.scancode {
+            0x3B..=0x44 => Key::Function(self.scancode - 0x3B + 1),
+            0x85 => Key::Function(11),
+            0x86 => Key::Function(12),
+            0x48 => Key::Up,
+            0x50 => Key::Down,
+            0x4B => Key::Left,
+            0x4D => Key::Right,
+            0x47 => Key::Home,
+            0x4F => Key::End,
+            0x49 => Key::PageUp,
+            0x51 => Key::PageDown,
+            0x52 => Key::Insert,
+            0x53 => Key::Delete,
+            _ => Key::Other(*self),
+        }
+    }
+}
+
+/// A key, as decoded by [KeyPress::key].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// A character, in the current code page, including control characters such as Ctrl-C (03h)
+    Char(u8),
+    /// F1 to F12, without shift keys. F11 and F12 are only returned by enhanced keyboards
+    Function(u8),
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+    Enter,
+    Escape,
+    Backspace,
+    Tab,
+    /// Another key without ASCII code, such as Alt-X or Shift-F1
+    Other(KeyPress),
+}
+
+/// State of the shift keys, returned by [shift_status].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShiftFlags(u8);
+
+impl ShiftFlags {
+    pub const RIGHT_SHIFT: ShiftFlags = ShiftFlags(0x01);
+    pub const LEFT_SHIFT: ShiftFlags = ShiftFlags(0x02);
+    pub const SHIFT: ShiftFlags = ShiftFlags(0x03);
+    pub const CTRL: ShiftFlags = ShiftFlags(0x04);
+    pub const ALT: ShiftFlags = ShiftFlags(0x08);
+    pub const SCROLL_LOCK: ShiftFlags = ShiftFlags(0x10);
+    pub const NUM_LOCK: ShiftFlags = ShiftFlags(0x20);
+    pub const CAPS_LOCK: ShiftFlags = ShiftFlags(0x40);
+    pub const INSERT: ShiftFlags = ShiftFlags(0x80);
+
+    pub const fn from_bits(bits: u8) -> Self {
+        ShiftFlags(bits)
+    }
+
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn contains(&self, other: ShiftFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns true if any of the flags in `other` is set, so that `intersects(ShiftFlags::SHIFT)` matches either shift key.
+    pub const fn intersects(&self, other: ShiftFlags) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl BitOr for ShiftFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        ShiftFlags(self.0 | rhs.0)
+    }
+}
+
+/// Waits for a key and removes it from the keyboard buffer (INT 16h AH=00h).
+pub fn read_key() -> KeyPress {
+    let key: u16;
+    unsafe { asm!("int 0x16", inout("ax") 0x0000_u16 => key) }
+    KeyPress::from_ax(key)
+}
+
+/// Gets the next key of the keyboard buffer without removing it, or None if the buffer is empty (INT 16h AH=01h).
+/// The key is then returned by [read_key].
+pub fn peek_key() -> Option<KeyPress> {
+    let no_key: u8;
+    let key: u16;
+    unsafe {
+        asm!("int 0x16", "setz {no_key}",
+             no_key = out(reg_byte) no_key,
+             inout("ax") 0x0100_u16 => key);
+    }
+    if no_key != 0 {
+        return None;
+    }
+    Some(KeyPress::from_ax(key))
+}
+
+/// Returns true if a key is waiting in the keyboard buffer, without removing it (INT 16h AH=01h).
+pub fn key_available() -> bool {
+    peek_key().is_some()
+}
+
+/// Gets the state of the shift keys (INT 16h AH=02h).
+pub fn shift_status() -> ShiftFlags {
+    let flags: u16;
+    unsafe { asm!("int 0x16", inout("ax") 0x0200_u16 => flags) }
+    ShiftFlags::from_bits(flags as u8)
+}
+
+/// Characters of the main keys of a US keyboard, indexed by scancode, 0 for the keys without one.
+const UNSHIFTED: &[u8; 0x3A] = b"\x00\x001234567890-=\x00\x00\
+                                 qwertyuiop[]\x00\x00as\
+                                 dfghjkl;'`\x00\\zxcv\
+                                 bnm,./\x00*\x00 ";
+const SHIFTED: &[u8; 0x3A] = b"\x00\x00!@#$%^&*()_+\x00\x00\
+                               QWERTYUIOP{}\x00\x00AS\
+                               DFGHJKL:\"~\x00|ZXCV\
+                               BNM<>?\x00*\x00 ";
+/// Characters of the keypad, from 47h.
+const KEYPAD: &[u8; 13] = b"789-456+1230.";
+
+/// Translates a scancode into the character of a US keyboard, given the state of the shift keys,
+/// for programs reading the raw keyboard (such as with [crate::dpkey]) or building their own line editor.
+///
+/// Caps Lock only affects letters, and cancels Shift for them. The digits and the dot of the keypad are given
+/// when either Num Lock or Shift is on, and None otherwise, as the keys then move the cursor; its minus and plus
+/// are always given. Ctrl and Alt are ignored.
+/// Returns None for the keys that don't print a character, including Enter, Tab, Backspace and Escape.
+/// The grey navigation keys of enhanced keyboards have the scancodes of the keypad, with an E0h prefix
+/// that isn't seen here.
+pub fn translate(scancode: u8, shift: ShiftFlags) -> Option<char> {
+    let shifted = shift.intersects(ShiftFlags::SHIFT);
+    let c = match scancode {
+        0x00..=0x39 => {
+            let unshifted = UNSHIFTED[scancode as usize];
+            let caps_lock = unshifted.is_ascii_lowercase() && shift.contains(ShiftFlags::CAPS_LOCK);
+            if shifted != caps_lock { SHIFTED[scancode as usize] } else { unshifted }
+        }
+        0x47..=0x53 => match KEYPAD[(scancode - 0x47) as usize] {
+            c @ (b'-' | b'+') => c,
+            c if shifted != shift.contains(ShiftFlags::NUM_LOCK) => c,
+            _ => 0,
+        },
+        _ => 0,
+    };
+    (c != 0).then_some(c as char)
+}
@@ -0,0 +1,68 @@
+//! BIOS printer services (INT 17h), reporting the printer state that DOS hides.
+//! Ports are numbered from 0 (LPT1).
+
+use core::arch::asm;
+use core::ops::BitOr;
+
+/// Status of a printer port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrinterStatus(u8);
+
+impl PrinterStatus {
+    pub const TIMEOUT: PrinterStatus = PrinterStatus(0x01);
+    pub const IO_ERROR: PrinterStatus = PrinterStatus(0x08);
+    pub const SELECTED: PrinterStatus = PrinterStatus(0x10);
+    pub const OUT_OF_PAPER: PrinterStatus = PrinterStatus(0x20);
+    pub const ACKNOWLEDGE: PrinterStatus = PrinterStatus(0x40);
+    /// Set when the printer is ready for the next byte
+    pub const NOT_BUSY: PrinterStatus = PrinterStatus(0x80);
+
+    pub const fn from_bits(bits: u8) -> Self {
+        PrinterStatus(bits)
+    }
+
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn contains(&self, other: PrinterStatus) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn is_busy(&self) -> bool {
+        !self.contains(Self::NOT_BUSY)
+    }
+
+    pub const fn out_of_paper(&self) -> bool {
+        self.contains(Self::OUT_OF_PAPER)
+    }
+}
+
+impl BitOr for PrinterStatus {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        PrinterStatus(self.0 | rhs.0)
+    }
+}
+
+fn printer_function(function: u8, port: u8, byte: u8) -> PrinterStatus {
+    let status: u16;
+    unsafe { asm!("int 0x17", inout("ax") (function as u16) << 8 | byte as u16 => status, in("dx") port as u16) }
+    PrinterStatus::from_bits((status >> 8) as u8)
+}
+
+/// Sends a byte to the printer (INT 17h AH=00h). [PrinterStatus::TIMEOUT] is set if the printer didn't take it.
+pub fn write(port: u8, byte: u8) -> PrinterStatus {
+    printer_function(0x00, port, byte)
+}
+
+/// Resets the printer (INT 17h AH=01h).
+pub fn init(port: u8) -> PrinterStatus {
+    printer_function(0x01, port, 0)
+}
+
+/// Gets the status of the printer (INT 17h AH=02h), to check it has paper before sending.
+pub fn status(port: u8) -> PrinterStatus {
+    printer_function(0x02, port, 0)
+}
@@ -0,0 +1,44 @@
+//! Real-time clock (INT 1Ah AH=02h and 04h), the battery-backed clock of AT-class machines.
+//! DOS reads it at boot and then counts timer ticks, so its date and time can drift from the RTC.
+
+use core::arch::asm;
+use crate::dos::datetime::{Date, Time};
+
+/// Decodes a packed BCD byte, each nibble being a decimal digit.
+fn from_bcd(value: u8) -> u8 {
+    (value >> 4) * 10 + (value & 0x0F)
+}
+
+/// Calls an RTC function, returning CX and DX, or None if the clock isn't running.
+fn rtc_function(function: u8) -> Option<(u16, u16)> {
+    let cx: u16;
+    let dx: u16;
+    let carry: u8;
+    unsafe {
+        // Some BIOSes leave the carry unchanged on success
+        asm!("clc", "int 0x1a", "setc {carry}",
+             carry = out(reg_byte) carry,
+             inout("ax") (function as u16) << 8 => _,
+             out("cx") cx,
+             out("dx") dx);
+    }
+    if carry != 0 {
+        return None;
+    }
+    Some((cx, dx))
+}
+
+/// Reads the time of the RTC (INT 1Ah AH=02h). Returns None if the clock isn't running, or on PCs and XTs.
+pub fn get_rtc_time() -> Option<Time> {
+    // BCD hours in CH, minutes in CL, seconds in DH
+    let (cx, dx) = rtc_function(0x02)?;
+    Some(Time::new(from_bcd((cx >> 8) as u8), from_bcd(cx as u8), from_bcd((dx >> 8) as u8)))
+}
+
+/// Reads the date of the RTC (INT 1Ah AH=04h). Returns None if the clock isn't running, or on PCs and XTs.
+pub fn get_rtc_date() -> Option<Date> {
+    // BCD century in CH, year in CL, month in DH, day in DL
+    let (cx, dx) = rtc_function(0x04)?;
+    let year = from_bcd((cx >> 8) as u8) as u16 * 100 + from_bcd(cx as u8) as u16;
+    Some(Date::new(year, from_bcd((dx >> 8) as u8), from_bcd(dx as u8)))
+}
@@ -0,0 +1,177 @@
+//! BIOS serial port services (INT 14h), with the line and modem status DOS doesn't give.
+//! Ports are numbered from 0 (COM1).
+
+use core::arch::asm;
+use core::ops::BitOr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaudRate {
+    Baud110 = 0,
+    Baud150 = 1,
+    Baud300 = 2,
+    Baud600 = 3,
+    Baud1200 = 4,
+    Baud2400 = 5,
+    Baud4800 = 6,
+    Baud9600 = 7,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    None = 0,
+    Odd = 1,
+    Even = 3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopBits {
+    One = 0,
+    Two = 1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataBits {
+    Seven = 2,
+    Eight = 3,
+}
+
+/// Settings of a port, 9600 bauds 8N1 by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SerialParams {
+    pub baud_rate: BaudRate,
+    pub parity: Parity,
+    pub stop_bits: StopBits,
+    pub data_bits: DataBits,
+}
+
+impl SerialParams {
+    /// Settings as encoded in AL for [init].
+    pub const fn bits(&self) -> u8 {
+        (self.baud_rate as u8) << 5 | (self.parity as u8) << 3 | (self.stop_bits as u8) << 2 | self.data_bits as u8
+    }
+}
+
+impl Default for SerialParams {
+    fn default() -> Self {
+        SerialParams {
+            baud_rate: BaudRate::Baud9600,
+            parity: Parity::None,
+            stop_bits: StopBits::One,
+            data_bits: DataBits::Eight,
+        }
+    }
+}
+
+/// Line status register of a port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineStatus(u8);
+
+impl LineStatus {
+    pub const DATA_READY: LineStatus = LineStatus(0x01);
+    pub const OVERRUN_ERROR: LineStatus = LineStatus(0x02);
+    pub const PARITY_ERROR: LineStatus = LineStatus(0x04);
+    pub const FRAMING_ERROR: LineStatus = LineStatus(0x08);
+    pub const BREAK: LineStatus = LineStatus(0x10);
+    pub const TRANSMIT_HOLDING_EMPTY: LineStatus = LineStatus(0x20);
+    pub const TRANSMIT_SHIFT_EMPTY: LineStatus = LineStatus(0x40);
+    /// Set by the BIOS when sending or receiving timed out
+    pub const TIMEOUT: LineStatus = LineStatus(0x80);
+    /// Any error or timeout
+    pub const ERRORS: LineStatus = LineStatus(0x8E);
+
+    pub const fn from_bits(bits: u8) -> Self {
+        LineStatus(bits)
+    }
+
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn contains(&self, other: LineStatus) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns true if any of the flags in `other` is set, so that `intersects(LineStatus::ERRORS)` matches any error.
+    pub const fn intersects(&self, other: LineStatus) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl BitOr for LineStatus {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        LineStatus(self.0 | rhs.0)
+    }
+}
+
+/// Modem status register of a port. The DELTA flags are set when the matching line changed since the last read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModemStatus(u8);
+
+impl ModemStatus {
+    pub const DELTA_CLEAR_TO_SEND: ModemStatus = ModemStatus(0x01);
+    pub const DELTA_DATA_SET_READY: ModemStatus = ModemStatus(0x02);
+    pub const TRAILING_EDGE_RING: ModemStatus = ModemStatus(0x04);
+    pub const DELTA_CARRIER_DETECT: ModemStatus = ModemStatus(0x08);
+    pub const CLEAR_TO_SEND: ModemStatus = ModemStatus(0x10);
+    pub const DATA_SET_READY: ModemStatus = ModemStatus(0x20);
+    pub const RING: ModemStatus = ModemStatus(0x40);
+    pub const CARRIER_DETECT: ModemStatus = ModemStatus(0x80);
+
+    pub const fn from_bits(bits: u8) -> Self {
+        ModemStatus(bits)
+    }
+
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn contains(&self, other: ModemStatus) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for ModemStatus {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        ModemStatus(self.0 | rhs.0)
+    }
+}
+
+fn status_from_ax(ax: u16) -> (LineStatus, ModemStatus) {
+    (LineStatus::from_bits((ax >> 8) as u8), ModemStatus::from_bits(ax as u8))
+}
+
+/// Sets the baud rate, parity, stop and data bits of a port (INT 14h AH=00h).
+pub fn init(port: u8, params: SerialParams) -> (LineStatus, ModemStatus) {
+    let status: u16;
+    unsafe { asm!("int 0x14", inout("ax") params.bits() as u16 => status, in("dx") port as u16) }
+    status_from_ax(status)
+}
+
+/// Sends a byte (INT 14h AH=01h). [LineStatus::TIMEOUT] is set if it couldn't be sent.
+pub fn send(port: u8, byte: u8) -> LineStatus {
+    let status: u16;
+    unsafe { asm!("int 0x14", inout("ax") 0x0100_u16 | byte as u16 => status, in("dx") port as u16) }
+    LineStatus::from_bits((status >> 8) as u8)
+}
+
+/// Waits for a byte, until the BIOS timeout (INT 14h AH=02h). Fails with the line status on a timeout or error.
+pub fn receive(port: u8) -> Result<u8, LineStatus> {
+    let result: u16;
+    unsafe { asm!("int 0x14", inout("ax") 0x0200_u16 => result, in("dx") port as u16) }
+    let line_status = LineStatus::from_bits((result >> 8) as u8);
+    if line_status.intersects(LineStatus::ERRORS) {
+        return Err(line_status);
+    }
+    Ok(result as u8)
+}
+
+/// Gets the line and modem status of a port (INT 14h AH=03h).
+pub fn status(port: u8) -> (LineStatus, ModemStatus) {
+    let status: u16;
+    unsafe { asm!("int 0x14", inout("ax") 0x0300_u16 => status, in("dx") port as u16) }
+    status_from_ax(status)
+}
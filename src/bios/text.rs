@@ -0,0 +1,57 @@
+//! Colors and attribute bytes of the text modes, for [crate::bios::text_screen] and [crate::bios::video].
+
+/// The 16 CGA colors. Only the first 8 can be used as background colors while blinking is enabled,
+/// which is the default: bright backgrounds blink instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black = 0,
+    Blue = 1,
+    Green = 2,
+    Cyan = 3,
+    Red = 4,
+    Magenta = 5,
+    Brown = 6,
+    LightGray = 7,
+    DarkGray = 8,
+    LightBlue = 9,
+    LightGreen = 10,
+    LightCyan = 11,
+    LightRed = 12,
+    LightMagenta = 13,
+    Yellow = 14,
+    White = 15,
+}
+
+/// An attribute byte: foreground color in bits 0-3, background color in bits 4-6, blinking in bit 7.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attribute(u8);
+
+impl Attribute {
+    const BLINK: u8 = 0x80;
+
+    pub const fn new(foreground: Color, background: Color) -> Self {
+        Attribute((background as u8) << 4 | foreground as u8)
+    }
+
+    /// Same attribute, with the character blinking.
+    pub const fn blinking(&self) -> Self {
+        Attribute(self.0 | Self::BLINK)
+    }
+
+    pub const fn into_byte(self) -> u8 {
+        self.0
+    }
+}
+
+impl Default for Attribute {
+    /// Light gray on black, as set by DOS.
+    fn default() -> Self {
+        Attribute::new(Color::LightGray, Color::Black)
+    }
+}
+
+impl From<Attribute> for u8 {
+    fn from(attribute: Attribute) -> Self {
+        attribute.into_byte()
+    }
+}
@@ -0,0 +1,212 @@
+//! Direct access to the color text mode buffer at B800:0000, much faster than going through DOS or the BIOS.
+//! Each cell is a `u16` holding the character in the low byte and its attribute in the high byte,
+//! given as a byte or a [crate::bios::text::Attribute].
+//! [Screen] draws into a back buffer, and only copies the changed cells to the screen.
+
+use core::ptr::{read_volatile, write_volatile};
+use crate::dos::error_code::ErrorCode;
+use crate::dos::far_ptr::FarPtr;
+use crate::dos::output::Output;
+
+/// The 80x25 color text screen. Writes outside of the screen are ignored.
+///
+/// As an [Output], it writes at its own cursor, which starts at the top left corner and isn't the BIOS cursor:
+/// lines wrap, CR and LF move the cursor, and the screen scrolls up past the last line.
+pub struct TextScreen {
+    buffer: *mut u16,
+    row: usize,
+    col: usize,
+    attr: u8,
+}
+
+impl TextScreen {
+    pub const WIDTH: usize = 80;
+    pub const HEIGHT: usize = 25;
+    const BUFFER_SEGMENT: u16 = 0xB800;
+
+    /// The screen must be in a color text mode (such as [crate::bios::video::VideoMode::Text80x25]).
+    pub fn new() -> Self {
+        TextScreen {
+            buffer: FarPtr::new(Self::BUFFER_SEGMENT, 0).as_mut_ptr(),
+            row: 0,
+            col: 0,
+            attr: 0x07,
+        }
+    }
+
+    /// Moves the cursor of [Output], clamped to the screen.
+    pub fn set_cursor(&mut self, row: usize, col: usize) {
+        self.row = row.min(Self::HEIGHT - 1);
+        self.col = col.min(Self::WIDTH - 1);
+    }
+
+    /// Position of the cursor of [Output], as (row, col). The column is [TextScreen::WIDTH] after filling a line,
+    /// which only wraps when the next character is written.
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    /// Sets the attribute of the characters written as an [Output], light gray on black by default.
+    pub fn set_attribute(&mut self, attr: impl Into<u8>) {
+        self.attr = attr.into();
+    }
+
+    /// A cell with a character and its attribute.
+    pub fn cell(ch: u8, attr: impl Into<u8>) -> u16 {
+        ((attr.into() as u16) << 8) | ch as u16
+    }
+
+    /// Writes a character with the given attribute at (`row`, `col`).
+    pub fn put(&mut self, row: usize, col: usize, ch: u8, attr: impl Into<u8>) {
+        if row >= Self::HEIGHT || col >= Self::WIDTH {
+            return;
+        }
+        unsafe {
+            write_volatile(self.buffer.add(row * Self::WIDTH + col), Self::cell(ch, attr));
+        }
+    }
+
+    /// Fills the screen with spaces of the given attribute.
+    pub fn clear(&mut self, attr: impl Into<u8>) {
+        let attr = attr.into();
+        for row in 0..Self::HEIGHT {
+            for col in 0..Self::WIDTH {
+                self.put(row, col, b' ', attr);
+            }
+        }
+    }
+
+    /// Writes a string starting at (`row`, `col`), without wrapping. Characters past the end of the line are dropped.
+    pub fn write_str_at(&mut self, row: usize, col: usize, s: &str, attr: impl Into<u8>) {
+        let attr = attr.into();
+        for (i, ch) in s.bytes().enumerate() {
+            self.put(row, col + i, ch, attr);
+        }
+    }
+
+    /// Scrolls the screen up by a line, clearing the last one with the attribute of [Output].
+    fn scroll_up(&mut self) {
+        for i in Self::WIDTH..CELL_COUNT {
+            unsafe { write_volatile(self.buffer.add(i - Self::WIDTH), read_volatile(self.buffer.add(i))) };
+        }
+        for col in 0..Self::WIDTH {
+            self.put(Self::HEIGHT - 1, col, b' ', self.attr);
+        }
+    }
+
+    fn new_line(&mut self) {
+        if self.row == Self::HEIGHT - 1 {
+            self.scroll_up();
+        } else {
+            self.row += 1;
+        }
+    }
+}
+
+impl Output for TextScreen {
+    /// Never fails.
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), ErrorCode> {
+        for &byte in bytes {
+            match byte {
+                b'\r' => self.col = 0,
+                b'\n' => self.new_line(),
+                _ => {
+                    if self.col == Self::WIDTH {
+                        self.col = 0;
+                        self.new_line();
+                    }
+                    self.put(self.row, self.col, byte, self.attr);
+                    self.col += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for TextScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const CELL_COUNT: usize = TextScreen::WIDTH * TextScreen::HEIGHT;
+
+/// A double-buffered 80x25 color text screen: drawing goes to a back buffer, and [Screen::flush] copies the cells
+/// that changed since the last flush. Writes outside of the screen are ignored.
+///
+/// The buffers take 8 KB. Writing to the screen by other means, such as `println!`, isn't noticed:
+/// call [Screen::invalidate] before the next flush to redraw everything.
+pub struct Screen {
+    buffer: *mut u16,
+    back: [u16; CELL_COUNT],
+    /// Contents of the screen as of the last flush
+    front: [u16; CELL_COUNT],
+}
+
+impl Screen {
+    /// Starts with the current contents of the screen, which must be in a color text mode.
+    pub fn new() -> Self {
+        let buffer: *mut u16 = FarPtr::new(TextScreen::BUFFER_SEGMENT, 0).as_mut_ptr();
+        let mut front = [0; CELL_COUNT];
+        for (i, cell) in front.iter_mut().enumerate() {
+            *cell = unsafe { read_volatile(buffer.add(i)) };
+        }
+        Screen { buffer, back: front, front }
+    }
+
+    /// Sets the cell at (`row`, `col`), made with [TextScreen::cell].
+    pub fn set(&mut self, row: usize, col: usize, cell: u16) {
+        if row >= TextScreen::HEIGHT || col >= TextScreen::WIDTH {
+            return;
+        }
+        self.back[row * TextScreen::WIDTH + col] = cell;
+    }
+
+    /// Cell at (`row`, `col`) in the back buffer.
+    pub fn get(&self, row: usize, col: usize) -> Option<u16> {
+        if row >= TextScreen::HEIGHT || col >= TextScreen::WIDTH {
+            return None;
+        }
+        Some(self.back[row * TextScreen::WIDTH + col])
+    }
+
+    /// Writes a string starting at (`row`, `col`), without wrapping. Characters past the end of the line are dropped.
+    pub fn write_str(&mut self, row: usize, col: usize, s: &str, attr: impl Into<u8>) {
+        let attr = attr.into();
+        for (i, ch) in s.bytes().enumerate() {
+            self.set(row, col + i, TextScreen::cell(ch, attr));
+        }
+    }
+
+    /// Fills the back buffer with spaces of the given attribute.
+    pub fn clear(&mut self, attr: impl Into<u8>) {
+        self.back = [TextScreen::cell(b' ', attr); CELL_COUNT];
+    }
+
+    /// Makes the next flush redraw every cell.
+    pub fn invalidate(&mut self) {
+        for (i, cell) in self.front.iter_mut().enumerate() {
+            *cell = !self.back[i];
+        }
+    }
+
+    /// Copies the cells changed since the last flush to the screen. Returns the number of cells written.
+    pub fn flush(&mut self) -> usize {
+        let mut cells_written = 0;
+        for i in 0..CELL_COUNT {
+            if self.back[i] != self.front[i] {
+                unsafe { write_volatile(self.buffer.add(i), self.back[i]) };
+                self.front[i] = self.back[i];
+                cells_written += 1;
+            }
+        }
+        cells_written
+    }
+}
+
+impl Default for Screen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
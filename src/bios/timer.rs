@@ -0,0 +1,71 @@
+//! BIOS timer services (INT 1Ah), counting ticks of about 54.925 ms (18.2 Hz) since midnight.
+
+use core::arch::asm;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Period of a tick, in microseconds.
+pub const TICK_MICROS: u32 = 54925;
+
+/// Value of the tick count at midnight, when it starts over from 0.
+pub const TICKS_PER_DAY: u32 = 0x1800B0;
+
+// Reading the counter clears the BIOS midnight flag, so keep it until asked for.
+static MIDNIGHT_PASSED: AtomicBool = AtomicBool::new(false);
+
+fn read_timer() -> u32 {
+    let high: u16;
+    let low: u16;
+    let midnight: u16;
+    unsafe {
+        asm!("int 0x1a",
+             inout("ax") 0x0000_u16 => midnight,
+             out("cx") high,
+             out("dx") low);
+    }
+    if midnight as u8 != 0 {
+        MIDNIGHT_PASSED.store(true, Ordering::Relaxed);
+    }
+    (high as u32) << 16 | low as u32
+}
+
+/// Reads the number of ticks since midnight (INT 1Ah AH=00h).
+pub fn read_tick_count() -> u32 {
+    read_timer()
+}
+
+/// Returns true if midnight passed since the last call, in which case the tick count started over from 0.
+/// The flag is kept even if it was cleared by [read_tick_count].
+pub fn midnight_rollover() -> bool {
+    read_timer();
+    MIDNIGHT_PASSED.swap(false, Ordering::Relaxed)
+}
+
+/// Converts a number of ticks to milliseconds.
+pub fn ticks_to_millis(ticks: u32) -> u32 {
+    (ticks as u64 * TICK_MICROS as u64 / 1000) as u32
+}
+
+/// Converts a number of milliseconds to ticks, rounding up.
+pub fn millis_to_ticks(millis: u32) -> u32 {
+    ((millis as u64 * 1000).div_ceil(TICK_MICROS as u64)) as u32
+}
+
+/// Waits for the tick count to advance by `ticks`, busy-waiting. The first tick may last from 0 to 55 ms,
+/// so the delay is between `ticks - 1` and `ticks` tick periods.
+pub fn sleep(ticks: u32) {
+    let start = read_timer();
+    loop {
+        let now = read_timer();
+        // The count starts over from 0 at midnight
+        let elapsed = if now >= start { now - start } else { now + TICKS_PER_DAY - start };
+        if elapsed >= ticks {
+            break;
+        }
+    }
+}
+
+/// Waits for at least `millis` milliseconds, rounded up to a whole number of ticks, busy-waiting.
+pub fn sleep_ms(millis: u32) {
+    // One more tick, since the first one is partial
+    sleep(millis_to_ticks(millis) + 1);
+}
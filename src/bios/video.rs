@@ -0,0 +1,132 @@
+//! BIOS video services (INT 10h), and direct access to the mode 13h framebuffer and the VGA palette.
+
+use core::arch::asm;
+use core::ptr::write_volatile;
+use crate::dos::far_ptr::FarPtr;
+use crate::dos::io::outb;
+
+/// Standard video modes, set with [set_mode].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoMode {
+    /// 40x25 text, 16 shades of gray
+    Text40x25Gray = 0x00,
+    /// 40x25 text, 16 colors
+    Text40x25 = 0x01,
+    /// 80x25 text, 16 shades of gray
+    Text80x25Gray = 0x02,
+    /// 80x25 text, 16 colors
+    Text80x25 = 0x03,
+    /// CGA 320x200, 4 colors
+    Cga320x200 = 0x04,
+    /// CGA 320x200, 4 shades of gray
+    Cga320x200Gray = 0x05,
+    /// CGA 640x200, 2 colors
+    Cga640x200 = 0x06,
+    /// Monochrome 80x25 text (MDA, Hercules)
+    Text80x25Mono = 0x07,
+    /// EGA 320x200, 16 colors
+    Ega320x200 = 0x0D,
+    /// EGA 640x200, 16 colors
+    Ega640x200 = 0x0E,
+    /// EGA 640x350, monochrome
+    Ega640x350Mono = 0x0F,
+    /// EGA 640x350, 16 colors
+    Ega640x350 = 0x10,
+    /// VGA 640x480, 2 colors
+    Vga640x480Mono = 0x11,
+    /// VGA 640x480, 16 colors
+    Vga640x480 = 0x12,
+    /// VGA 320x200, 256 colors
+    Vga320x200 = 0x13,
+}
+
+/// Sets the video mode, clearing the screen (INT 10h AH=00h).
+pub fn set_mode(mode: VideoMode) {
+    unsafe {
+        asm!("push bp", "int 0x10", "pop bp",
+             inout("ax") mode as u16 => _);
+    }
+}
+
+/// Gets the display page being shown (INT 10h AH=0Fh).
+pub fn get_active_page() -> u8 {
+    let page: u16;
+    unsafe {
+        asm!("int 0x10",
+             inout("ax") 0x0F00_u16 => _,
+             out("bx") page);
+    }
+    (page >> 8) as u8
+}
+
+/// Moves the cursor of the given display page (INT 10h AH=02h). Row and column start at 0.
+pub fn set_cursor_position(page: u8, row: u8, col: u8) {
+    unsafe {
+        asm!("int 0x10",
+             inout("ax") 0x0200_u16 => _,
+             in("bx") (page as u16) << 8,
+             in("dx") ((row as u16) << 8) | col as u16);
+    }
+}
+
+/// Gets the cursor position of the given display page as (row, column) (INT 10h AH=03h).
+pub fn get_cursor_position(page: u8) -> (u8, u8) {
+    let position: u16;
+    unsafe {
+        asm!("int 0x10",
+             inout("ax") 0x0300_u16 => _,
+             in("bx") (page as u16) << 8,
+             out("cx") _,
+             out("dx") position);
+    }
+    ((position >> 8) as u8, position as u8)
+}
+
+/// Writes `count` times the character `ch` with the attribute `attr` at the cursor position, on page 0 (INT 10h AH=09h).
+/// The cursor doesn't move. In graphics modes, `attr` is the foreground color.
+pub fn write_char_at(ch: u8, attr: impl Into<u8>, count: u16) {
+    let attr = attr.into();
+    unsafe {
+        asm!("int 0x10",
+             inout("ax") 0x0900_u16 | ch as u16 => _,
+             in("bx") attr as u16,
+             in("cx") count);
+    }
+}
+
+/// Width of mode 13h ([VideoMode::Vga320x200]), in pixels.
+pub const MODE_13H_WIDTH: u16 = 320;
+/// Height of mode 13h, in pixels.
+pub const MODE_13H_HEIGHT: u16 = 200;
+const MODE_13H_SEGMENT: u16 = 0xA000;
+const DAC_WRITE_INDEX_PORT: u16 = 0x3C8;
+const DAC_DATA_PORT: u16 = 0x3C9;
+
+/// Sets the pixel at (`x`, `y`) to a palette index, writing to the framebuffer at A000:0000.
+/// The screen must be in mode 13h. Pixels outside of the screen are ignored.
+pub fn put_pixel(x: u16, y: u16, color: u8) {
+    if x >= MODE_13H_WIDTH || y >= MODE_13H_HEIGHT {
+        return;
+    }
+    let framebuffer: *mut u8 = FarPtr::new(MODE_13H_SEGMENT, 0).as_mut_ptr();
+    unsafe { write_volatile(framebuffer.add(y as usize * MODE_13H_WIDTH as usize + x as usize), color) }
+}
+
+/// Fills the mode 13h screen with a palette index.
+pub fn clear_screen(color: u8) {
+    let framebuffer: *mut u8 = FarPtr::new(MODE_13H_SEGMENT, 0).as_mut_ptr();
+    for offset in 0..MODE_13H_WIDTH as usize * MODE_13H_HEIGHT as usize {
+        unsafe { write_volatile(framebuffer.add(offset), color) }
+    }
+}
+
+/// Sets a color of the 256-color palette through the VGA DAC. Components range from 0 to 63,
+/// higher bits are ignored. The palette is reset by [set_mode].
+pub fn set_palette_entry(index: u8, r: u8, g: u8, b: u8) {
+    unsafe {
+        outb(DAC_WRITE_INDEX_PORT, index);
+        outb(DAC_DATA_PORT, r & 0x3F);
+        outb(DAC_DATA_PORT, g & 0x3F);
+        outb(DAC_DATA_PORT, b & 0x3F);
+    }
+}
@@ -4,10 +4,26 @@ pub mod allocator;
 pub mod io;
 pub mod kbc;
 pub mod file;
+pub mod fcb;
+pub mod datetime;
 pub mod error_code;
+pub mod far_ptr;
+pub mod interrupt_vector;
 pub mod panic;
+pub mod process;
 pub mod math;
+pub mod memory;
 pub mod cooperative_multitasking;
+pub mod directory;
+pub mod disk;
+pub mod system;
+pub mod env;
+pub mod country;
+pub mod ioctl;
+pub mod serial;
+pub mod sysvars;
+pub mod critical_error;
+pub mod output;
 use core::arch::asm;
 
 pub use alloc::string::String as String;
@@ -15,10 +31,10 @@ pub use alloc::boxed::Box as Box;
 pub use alloc::vec::Vec as Vec;
 pub use alloc::vec as vec;
 
+/// Terminates the program with the given return code, readable as ERRORLEVEL from a batch file (INT 21h AH=4Ch).
 pub fn exit(rt: u8) -> ! {
     unsafe {
         asm!("mov ah, 0x4C",
-             "int 0x21", in("al") rt);
+             "int 0x21", in("al") rt, options(noreturn));
     }
-    loop {}
 }
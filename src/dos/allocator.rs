@@ -1,12 +1,19 @@
 //! Memory heap allocation for DOS programs.
-//! Uses conventional memory for DOS programs, from _heap segment start to extended BIOS data area (EBDA)
+//! At startup, the program's own memory block is shrunk to its 64 KB segment, and the largest free block of
+//! conventional memory is requested from DOS (INT 21h AH=48h) to be used as the heap, keeping
+//! [DosAllocator::RESERVED_PARAGRAPHS] (64 KB) free for [crate::dos::memory] and child programs.
+//! With DOS loaded low, the heap is usually around 500 KB.
 //! Uses linear algorithm for allocating memory, which is not optimal, but it's simple and works.
+//! When no block is large enough, allocations return a null pointer, which triggers the alloc_error_handler
+//! (and a panic) for the `alloc` collections.
 
 use core::alloc::{GlobalAlloc, Layout};
 use core::arch::asm;
 use core::cmp::min;
 use core::mem::size_of;
+use crate::dos::memory::DosMemory;
 
+// Blocks cover the whole heap: each block spans up to the next one, its header included.
 struct AllocatorBlock {
     next: Option<*mut AllocatorBlock>,
     prev: Option<*mut AllocatorBlock>,
@@ -19,26 +26,16 @@ pub struct DosAllocator {
 }
 
 impl DosAllocator {
-    const LAST_MEMORY_BYTE_ADDR: u32 = 0x9FBFF; // (0X9000 << 4) + 0XFBFF, last byte of memory before extended BIOS data area
-    const ALLOCATOR_BLOCK_SIZE: usize = size_of::<AllocatorBlock>();
+    /// Paragraphs of conventional memory left free for DOS allocations after the heap is created.
+    pub const RESERVED_PARAGRAPHS: u16 = 0x1000;
+    const PROGRAM_PARAGRAPHS: u16 = 0x1000; // the whole 64 KB segment, the stack is at its end
+    // Blocks start on a multiple of BLOCK_ALIGN, and the header keeps the useful pointer aligned as well
+    const BLOCK_ALIGN: usize = 16;
+    const ALLOCATOR_BLOCK_SIZE: usize = Self::align_up(size_of::<AllocatorBlock>(), Self::BLOCK_ALIGN);
     const MIN_BLOCK_USEFUL_SIZE: usize = 16;
 
-    fn diff_between_blocks_ptr(first_block: *mut AllocatorBlock, second_block: *mut AllocatorBlock) -> usize {
-        let first_block_addr = first_block as usize;
-        let second_block_addr = second_block as usize;
-        assert!(first_block_addr < second_block_addr);
-        second_block_addr - first_block_addr
-    }
-
-    fn free_space_before_next_block(block: *mut AllocatorBlock) -> usize {
-        assert_ne!(block, core::ptr::null_mut());
-        assert!((block as u32) < Self::LAST_MEMORY_BYTE_ADDR);
-        let next_block = unsafe { (*block).next };
-        if next_block.is_none() {
-            return Self::LAST_MEMORY_BYTE_ADDR as usize - block as usize;
-        }
-        let next_block = next_block.unwrap();
-        Self::diff_between_blocks_ptr(block, next_block) - Self::ALLOCATOR_BLOCK_SIZE
+    const fn align_up(value: usize, align: usize) -> usize {
+        (value + align - 1) & !(align - 1)
     }
 
     /// Converts block address to pointer usable by the program
@@ -46,89 +43,136 @@ impl DosAllocator {
         assert_ne!(block, core::ptr::null_mut());
         (block as usize + Self::ALLOCATOR_BLOCK_SIZE) as *mut u8
     }
+
+    fn useful_ptr_to_block_addr(ptr: *mut u8) -> *mut AllocatorBlock {
+        (ptr as usize - Self::ALLOCATOR_BLOCK_SIZE) as *mut AllocatorBlock
+    }
+
+    /// Size of the block needed for `size` useful bytes, header included
+    fn block_size_for(size: usize) -> usize {
+        Self::align_up(size + Self::ALLOCATOR_BLOCK_SIZE, Self::BLOCK_ALIGN)
+    }
+
+    /// Cuts the block at `offset`, creating a new unused block from there. Returns the new block.
+    unsafe fn split_at(block: *mut AllocatorBlock, offset: usize) -> *mut AllocatorBlock {
+        let new_block_ptr = (block as usize + offset) as *mut AllocatorBlock;
+        *new_block_ptr = AllocatorBlock {
+            next: (*block).next,
+            prev: Some(block),
+            size: (*block).size - offset,
+            used: false,
+        };
+        if let Some(next_block_ptr) = (*block).next {
+            (*next_block_ptr).prev = Some(new_block_ptr);
+        }
+        (*block).next = Some(new_block_ptr);
+        (*block).size = offset;
+        new_block_ptr
+    }
+
+    /// Shrinks the block to `size` if the rest is large enough to be useful, merging it with the next block if free.
+    unsafe fn shrink(block: *mut AllocatorBlock, size: usize) {
+        if (*block).size - size < Self::MIN_BLOCK_USEFUL_SIZE + Self::ALLOCATOR_BLOCK_SIZE {
+            return;
+        }
+        let new_block_ptr = Self::split_at(block, size);
+        Self::merge_with_next_if_free(new_block_ptr);
+    }
+
+    unsafe fn merge_with_next_if_free(block: *mut AllocatorBlock) {
+        let next_block_ptr = match (*block).next {
+            Some(ptr) if !(*ptr).used => ptr,
+            _ => return,
+        };
+        if let Some(next_next_block_ptr) = (*next_block_ptr).next {
+            (*next_next_block_ptr).prev = Some(block);
+        }
+        (*block).size += (*next_block_ptr).size;
+        (*block).next = (*next_block_ptr).next;
+    }
+
+    /// Finds where a block for `layout` fits in the free block, if it does.
+    /// Returns the address of the header, which is after the free block start when padding is needed for alignment.
+    unsafe fn fit_in_free_block(block: *mut AllocatorBlock, layout: Layout, block_size: usize) -> Option<usize> {
+        let start = block as usize;
+        let mut header = Self::align_up(start + Self::ALLOCATOR_BLOCK_SIZE, layout.align()) - Self::ALLOCATOR_BLOCK_SIZE;
+        if header != start && header - start < Self::MIN_BLOCK_USEFUL_SIZE + Self::ALLOCATOR_BLOCK_SIZE {
+            // The padding must be large enough to become a free block of its own
+            header = Self::align_up(start + Self::MIN_BLOCK_USEFUL_SIZE + 2 * Self::ALLOCATOR_BLOCK_SIZE, layout.align())
+                - Self::ALLOCATOR_BLOCK_SIZE;
+        }
+        if header + block_size > start + (*block).size {
+            return None;
+        }
+        Some(header)
+    }
 }
 
 unsafe impl GlobalAlloc for DosAllocator {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let block_size = Self::block_size_for(layout.size());
+
         // Look for next free block
         let mut current_block_ptr = self.first_block_ptr;
-        while (*current_block_ptr).used || (*current_block_ptr).size < layout.size() + Self::ALLOCATOR_BLOCK_SIZE {
+        let header = loop {
+            if !(*current_block_ptr).used {
+                if let Some(header) = Self::fit_in_free_block(current_block_ptr, layout, block_size) {
+                    break header;
+                }
+            }
             current_block_ptr = match (*current_block_ptr).next {
                 Some(ptr) => ptr,
                 None => return core::ptr::null_mut(), // No free block found, return null ptr
             };
-        }
+        };
 
-        let free_space_before_next_block = Self::free_space_before_next_block(current_block_ptr);
-        if free_space_before_next_block <= Self::MIN_BLOCK_USEFUL_SIZE + Self::ALLOCATOR_BLOCK_SIZE {
-            // No space for new block, just use the whole space
-            (*current_block_ptr).used = true;
-            (*current_block_ptr).size = free_space_before_next_block;
-            return Self::block_addr_to_useful_ptr(current_block_ptr);
+        if header != current_block_ptr as usize {
+            // Leave the alignment padding as a free block
+            current_block_ptr = Self::split_at(current_block_ptr, header - current_block_ptr as usize);
         }
-
-        // Create a new unused block between current and next
-
-        let current_block_size = layout.size() + Self::ALLOCATOR_BLOCK_SIZE;
-
-        // Create a new block just after current block
-        let new_block_ptr = (current_block_size + current_block_ptr as usize) as *mut AllocatorBlock;
-        (*new_block_ptr).next = (*current_block_ptr).next;
-        (*new_block_ptr).prev = Some(current_block_ptr);
-        (*new_block_ptr).size = free_space_before_next_block - current_block_size;
-        (*new_block_ptr).used = false;
-
-        (*current_block_ptr).next = Some(new_block_ptr);
-        (*current_block_ptr).size = current_block_size;
+        Self::shrink(current_block_ptr, block_size);
         (*current_block_ptr).used = true;
-
         Self::block_addr_to_useful_ptr(current_block_ptr)
     }
 
     unsafe fn dealloc(&self, ptr: *mut u8, _layout: Layout) {
         // Freeing null pointer is a no-op
-        if ptr == core::ptr::null_mut() {
+        if ptr.is_null() {
             return;
         }
 
-        let current_block_ptr = (ptr as u32 - Self::ALLOCATOR_BLOCK_SIZE as u32) as *mut AllocatorBlock;
+        let current_block_ptr = Self::useful_ptr_to_block_addr(ptr);
         // Mark block as free
         (*current_block_ptr).used = false;
 
-        // Merge with next block if it's free
-        let next_block_ptr = (*current_block_ptr).next;
-        if next_block_ptr.is_some() {
-            let next_block_ptr = next_block_ptr.unwrap();
-            if !(*next_block_ptr).used {
-                if (*next_block_ptr).next.is_some() {
-                    (*(*next_block_ptr).next.unwrap()).prev = Some(current_block_ptr);
-                }
-                (*current_block_ptr).size += (*next_block_ptr).size;
-                (*current_block_ptr).next = (*next_block_ptr).next;
-            }
-        }
-
-        // Merge with previous block if it's free
-        let prev_block_ptr = (*current_block_ptr).prev;
-        if prev_block_ptr.is_some() {
-            let prev_block_ptr = prev_block_ptr.unwrap();
+        Self::merge_with_next_if_free(current_block_ptr);
+        if let Some(prev_block_ptr) = (*current_block_ptr).prev {
             if !(*prev_block_ptr).used {
-                if (*current_block_ptr).next.is_some() {
-                    (*(*current_block_ptr).next.unwrap()).prev = Some(prev_block_ptr);
-                }
-                (*prev_block_ptr).size += (*current_block_ptr).size;
-                (*prev_block_ptr).next = (*current_block_ptr).next;
+                Self::merge_with_next_if_free(prev_block_ptr);
             }
         }
     }
 
     unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
         assert_ne!(ptr, core::ptr::null_mut()); // Avoid undefined behavior
-        let current_block_ptr = (ptr as u32 - Self::ALLOCATOR_BLOCK_SIZE as u32) as *mut AllocatorBlock;
-        if (*current_block_ptr).size >= new_size + Self::ALLOCATOR_BLOCK_SIZE {
+        let current_block_ptr = Self::useful_ptr_to_block_addr(ptr);
+        let block_size = Self::block_size_for(new_size);
+
+        // Grow in place if the next block is free and large enough
+        if let Some(next_block_ptr) = (*current_block_ptr).next {
+            if !(*next_block_ptr).used && (*current_block_ptr).size + (*next_block_ptr).size >= block_size {
+                Self::merge_with_next_if_free(current_block_ptr);
+            }
+        }
+        if (*current_block_ptr).size >= block_size {
+            Self::shrink(current_block_ptr, block_size);
             return ptr;
         }
-        let new_ptr = self.alloc(Layout::from_size_align(new_size, layout.align()).unwrap());
+
+        let new_ptr = self.alloc(Layout::from_size_align_unchecked(new_size, layout.align()));
+        if new_ptr.is_null() {
+            return new_ptr; // The old block is left untouched
+        }
         new_ptr.copy_from_nonoverlapping(ptr, min(layout.size(), new_size));
         self.dealloc(ptr, layout);
         new_ptr
@@ -136,27 +180,41 @@ unsafe impl GlobalAlloc for DosAllocator {
 }
 
 impl DosAllocator {
-    #[allow(unused_assignments)]
     pub fn init(&mut self) {
-        let mut heap_segment_number: u32 = 0;
+        // A COM program owns all the conventional memory, give back what is after its segment
+        let psp_segment: u16;
         unsafe {
-            asm!("mov ax, _heap", out("ax") heap_segment_number);
+            asm!("mov {0:x}, ds", out(reg) psp_segment);
+            asm!("push es", "mov es, {psp:x}", "mov ah, 0x4a", "int 0x21", "pop es",
+                 psp = in(reg) psp_segment,
+                 in("bx") Self::PROGRAM_PARAGRAPHS,
+                 out("ax") _);
         }
-        // Compute heap address from segment number
-        let heap_addr = ((heap_segment_number & 0xFFFF) << 4) as u32;
-        let heap_ptr_as_block = heap_addr as *mut AllocatorBlock;
+
+        // Asking for too much fails but gives the size of the largest block
+        let largest_block = match DosMemory::allocate(0xFFFF) {
+            Ok(memory) => memory.paragraphs(),
+            Err((_, largest_block)) => largest_block,
+        };
+        let heap_paragraphs = if largest_block > 2 * Self::RESERVED_PARAGRAPHS {
+            largest_block - Self::RESERVED_PARAGRAPHS
+        } else {
+            largest_block // Not much memory, keep it all for the heap
+        };
+        let heap = DosMemory::allocate(heap_paragraphs).unwrap_or_else(|_| panic!("no memory for the heap"));
+        let heap_ptr_as_block = heap.as_ptr() as *mut AllocatorBlock;
 
         // Create an empty block at the beginning of the heap, containing all free space
-        assert!(heap_addr as u32 <= DosAllocator::LAST_MEMORY_BYTE_ADDR);
-        let first_block_size = DosAllocator::LAST_MEMORY_BYTE_ADDR - heap_addr;
         unsafe {
             *heap_ptr_as_block = AllocatorBlock {
                 next: None,
                 prev: None,
-                size: first_block_size as usize,
+                size: heap.len(),
                 used: false,
             };
         }
+        // The heap is used until the program terminates
+        core::mem::forget(heap);
         self.first_block_ptr = heap_ptr_as_block
     }
 
@@ -173,4 +231,4 @@ fn alloc_error_handler(layout: Layout) -> ! {
 }
 
 #[global_allocator]
-pub(crate) static mut GLOBAL_ALLOCATOR: DosAllocator = DosAllocator::new();
\ No newline at end of file
+pub(crate) static mut GLOBAL_ALLOCATOR: DosAllocator = DosAllocator::new();
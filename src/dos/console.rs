@@ -1,5 +1,12 @@
 use core::arch::asm;
 use core::fmt::{self, Write};
+use core::cmp::min;
+use crate::bios::text_screen::TextScreen;
+use crate::bios::video::{get_active_page, get_cursor_position, set_cursor_position};
+use crate::dos::error_code::ErrorCode;
+use crate::dos::file::{File, STDOUT};
+use crate::dos::output::Output;
+use crate::dos::String;
 
 #[macro_export]
 macro_rules! print {
@@ -18,12 +25,34 @@ macro_rules! println {
     };
 }
 
+/// Prints at a row and column of the active page, clamped to 80x25, like [print!],
+/// and then moves the cursor back where it was.
+///
+/// ```ignore
+/// print_at!(0, 70, "{:>10}", score);
+/// ```
+#[macro_export]
+macro_rules! print_at {
+    ($row:expr, $col:expr, $($arg:tt)*) => {
+        $crate::dos::console::_print_at($row, $col, format_args!($($arg)*))
+    };
+}
+
 pub fn _print(args: fmt::Arguments) {
     let mut writer = DosWriter {};
     writer.write_fmt(args).unwrap();
 }
 
-struct DosWriter;
+/// The output goes through DOS, so it goes to the file instead if the standard output is redirected.
+pub fn _print_at(row: u8, col: u8, args: fmt::Arguments) {
+    let page = get_active_page();
+    let (saved_row, saved_col) = get_cursor_position(page);
+    set_cursor_position(page, min(row, TextScreen::HEIGHT as u8 - 1), min(col, TextScreen::WIDTH as u8 - 1));
+    _print(args);
+    set_cursor_position(page, saved_row, saved_col);
+}
+
+pub(crate) struct DosWriter;
 
 impl Write for DosWriter {
     fn write_str(&mut self, s: &str) -> fmt::Result {
@@ -37,3 +66,78 @@ impl Write for DosWriter {
 fn printc(ch: u8) {
     unsafe { asm!("int 0x21", in("ah") 0x02_u8, in("dl") ch) }
 }
+
+/// Longest line read by [Stdin::read_line], the limit of INT 21h AH=0Ah.
+pub const MAX_LINE_LENGTH: usize = 254;
+
+/// The standard input, read through DOS: it can be redirected, and Ctrl-C is checked while reading.
+pub struct Stdin {
+    line: String,
+}
+
+/// Gets a handle to the standard input.
+pub fn stdin() -> Stdin {
+    Stdin::new()
+}
+
+impl Stdin {
+    pub fn new() -> Self {
+        Stdin { line: String::with_capacity(MAX_LINE_LENGTH) }
+    }
+
+    /// Reads a line, with the DOS line editing keys (INT 21h AH=0Ah), and moves to the next line.
+    /// The line is returned without its CR, and stays valid until the next call, which reuses its buffer.
+    /// The bell rings when the line is full, and further keys are ignored until Enter is pressed.
+    pub fn read_line(&mut self) -> &str {
+        // Maximum length including the CR, length read, then the characters
+        let mut buffer: [u8; MAX_LINE_LENGTH + 3] = [0; MAX_LINE_LENGTH + 3]; // To be sure of the segment
+        buffer[0] = (MAX_LINE_LENGTH + 1) as u8;
+        unsafe {
+            asm!("mov ah, 0x0a", "int 0x21",
+                 in("dx") buffer.as_mut_ptr() as u16,
+                 out("ax") _);
+        }
+        // DOS only echoes the CR
+        printc(b'\n');
+        let length = buffer[1] as usize;
+        self.line.clear();
+        self.line.extend(buffer[2..2 + length].iter().map(|&c| c as char));
+        &self.line
+    }
+
+    /// Waits for a character without echoing it (INT 21h AH=08h).
+    /// Extended keys, such as the arrows, are read as 0 followed by their scan code.
+    pub fn read_char(&mut self) -> u8 {
+        let ch: u8;
+        unsafe { asm!("int 0x21", in("ah") 0x08_u8, lateout("al") ch) }
+        ch
+    }
+
+    /// Waits for a character and echoes it (INT 21h AH=01h).
+    pub fn read_char_echo(&mut self) -> u8 {
+        let ch: u8;
+        unsafe { asm!("int 0x21", in("ah") 0x01_u8, lateout("al") ch) }
+        ch
+    }
+}
+
+impl Default for Stdin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The standard output, written through its handle (INT 21h AH=40h) as an [Output]: it can be redirected,
+/// and unlike [print!], the errors are returned, such as a full disk when redirected to a file.
+pub struct Stdout;
+
+/// Gets a handle to the standard output.
+pub fn stdout() -> Stdout {
+    Stdout
+}
+
+impl Output for Stdout {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), ErrorCode> {
+        File::standard(STDOUT).write_all(bytes)
+    }
+}
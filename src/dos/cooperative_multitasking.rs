@@ -0,0 +1,142 @@
+//! Minimal cooperative scheduler.
+//! Tasks are registered with [spawn] and executed round-robin by [run], which returns once they have all finished.
+//! Scheduling is cooperative: there is no preemption, so each task must call [yield_now] regularly to let the others run.
+//!
+//! Each task has its own stack of [STACK_SIZE] bytes, and there are at most [MAX_TASKS] of them.
+//! The stacks are static arrays in the program's segment rather than allocated from the heap when spawning:
+//! in real mode push and pop only use SP, and pointers to locals are offsets from DS, so the stack must be
+//! in our 64 KB segment, while the heap is a separate DOS memory block above it.
+
+use core::arch::naked_asm;
+use core::ptr::{addr_of, addr_of_mut};
+
+pub const MAX_TASKS: usize = 4;
+pub const STACK_SIZE: usize = 2048;
+
+#[repr(C, align(16))]
+struct TaskStack([u8; STACK_SIZE]);
+
+#[derive(Clone, Copy)]
+struct TaskSlot {
+    entry: Option<fn()>, // None when the slot is free
+    esp: u32,
+}
+
+struct Scheduler {
+    tasks: [TaskSlot; MAX_TASKS],
+    current_task: Option<usize>,
+    scheduler_esp: u32,
+}
+
+// Only accessed through raw pointers, as the switches to and from the tasks happen in the middle of every function
+static mut SCHEDULER: Scheduler = Scheduler {
+    tasks: [TaskSlot { entry: None, esp: 0 }; MAX_TASKS],
+    current_task: None,
+    scheduler_esp: 0,
+};
+static mut STACKS: [TaskStack; MAX_TASKS] = [const { TaskStack([0; STACK_SIZE]) }; MAX_TASKS];
+
+/// Registers a task, like [crate::dos::cooperative_multitasking::spawn].
+#[macro_export]
+macro_rules! add_cooperative_task {
+    ($main_function:expr) => {
+        $crate::dos::cooperative_multitasking::spawn($main_function)
+    };
+}
+
+/// Lets the other tasks run, like [crate::dos::cooperative_multitasking::yield_now].
+#[macro_export]
+macro_rules! yield_cooperative_task {
+    () => {
+        $crate::dos::cooperative_multitasking::yield_now()
+    };
+}
+
+/// Saves the callee-saved registers and the stack pointer to `save_esp`, then resumes the context saved at `load_esp`.
+#[unsafe(naked)]
+unsafe extern "C" fn switch_context(save_esp: *mut u32, load_esp: u32) {
+    naked_asm!(
+        "push ebp", "push ebx", "push esi", "push edi",
+        "mov eax, [esp + 20]",
+        "mov [eax], esp",
+        "mov esp, [esp + 24]",
+        "pop edi", "pop esi", "pop ebx", "pop ebp",
+        "ret",
+    );
+}
+
+/// Switches from the task `task_index` back to [run].
+///
+/// # Safety
+/// Must be called from the task `task_index`.
+unsafe fn switch_to_scheduler(task_index: usize) {
+    unsafe { switch_context(addr_of_mut!(SCHEDULER.tasks[task_index].esp), addr_of!(SCHEDULER.scheduler_esp).read()) }
+}
+
+/// First function executed on the stack of a task.
+extern "C" fn task_start() -> ! {
+    let task_index = unsafe { addr_of!(SCHEDULER.current_task).read() }.unwrap();
+    let entry = unsafe { addr_of!(SCHEDULER.tasks[task_index].entry).read() }.unwrap();
+    entry();
+    unsafe {
+        // This stack is never resumed, so the slot can be reused right away
+        addr_of_mut!(SCHEDULER.tasks[task_index].entry).write(None);
+        switch_to_scheduler(task_index);
+    }
+    unreachable!();
+}
+
+/// Registers a task, which starts at the next round of [run]. Tasks can spawn other tasks.
+pub fn spawn(f: fn()) -> Result<(), &'static str> {
+    let tasks = unsafe { addr_of!(SCHEDULER.tasks).read() };
+    let task_index = match tasks.iter().position(|task| task.entry.is_none()) {
+        Some(task_index) => task_index,
+        None => return Err("Maximum number of tasks reached"),
+    };
+    // Initial context, as saved by switch_context: EDI, ESI, EBX, EBP, then the return address
+    // to task_start, and a fake return address for task_start itself.
+    let stack = unsafe { addr_of_mut!(STACKS[task_index].0) } as *mut u8;
+    let stack_top = (stack as usize + STACK_SIZE) as *mut u32;
+    unsafe {
+        stack_top.sub(1).write(0);
+        stack_top.sub(2).write(task_start as *const () as u32);
+        for i in 3..=6 {
+            stack_top.sub(i).write(0);
+        }
+        addr_of_mut!(SCHEDULER.tasks[task_index]).write(TaskSlot {
+            entry: Some(f),
+            esp: stack_top.sub(6) as u32,
+        });
+    }
+    Ok(())
+}
+
+/// Lets the other tasks run. Does nothing when called outside of a task.
+pub fn yield_now() {
+    if let Some(task_index) = unsafe { addr_of!(SCHEDULER.current_task).read() } {
+        unsafe { switch_to_scheduler(task_index) };
+    }
+}
+
+/// Runs the tasks round-robin, until they have all finished. The caller, usually `main`, isn't a task itself:
+/// it waits here.
+pub fn run() {
+    assert!(unsafe { addr_of!(SCHEDULER.current_task).read() }.is_none(), "run called from a task");
+    loop {
+        let mut any_task = false;
+        for task_index in 0..MAX_TASKS {
+            if unsafe { addr_of!(SCHEDULER.tasks[task_index].entry).read() }.is_none() {
+                continue;
+            }
+            any_task = true;
+            unsafe {
+                addr_of_mut!(SCHEDULER.current_task).write(Some(task_index));
+                switch_context(addr_of_mut!(SCHEDULER.scheduler_esp), addr_of!(SCHEDULER.tasks[task_index].esp).read());
+                addr_of_mut!(SCHEDULER.current_task).write(None);
+            }
+        }
+        if !any_task {
+            break;
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! Country dependent information, for formatting dates, times and amounts (INT 21h AH=38h),
+//! the code page used to display characters above 127 (INT 21h AH=66h), and the tables to
+//! compare and sort strings in the current country (INT 21h AH=65h), and the lead bytes of
+//! double-byte character sets (INT 21h AH=63h).
+
+use core::arch::asm;
+use core::ptr::{addr_of, addr_of_mut};
+use core::sync::atomic::{AtomicBool, Ordering};
+use crate::dos::error_code::ErrorCode;
+use crate::dos::far_ptr::FarPtr;
+use crate::dos::String;
+use crate::dos::system::dos_version_at_least;
+
+/// Order of the date fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateFormat {
+    /// Month, day, year
+    Usa = 0,
+    /// Day, month, year
+    Europe = 1,
+    /// Year, month, day
+    Japan = 2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeFormat {
+    Hours12 = 0,
+    Hours24 = 1,
+}
+
+/// Country information, as returned by [get_country_info].
+/// Characters above 127 depend on the code page, and are converted as if it was Latin-1.
+#[derive(Debug, Clone)]
+pub struct CountryInfo {
+    /// Usually the international phone prefix, such as 1 for the USA
+    pub country_code: u16,
+    pub date_format: DateFormat,
+    pub currency: String,
+    pub thousands_separator: char,
+    pub decimal_separator: char,
+    pub date_separator: char,
+    pub time_separator: char,
+    /// Bit 0 set if the currency symbol follows the amount, bit 1 set for a space between them
+    pub currency_format: u8,
+    /// Number of digits after the decimal separator in amounts
+    pub currency_digits: u8,
+    pub time_format: TimeFormat,
+    pub list_separator: char,
+}
+
+/// Gets the information of the current country (INT 21h AX=3800h, DOS 3.0+ layout).
+pub fn get_country_info() -> Result<CountryInfo, ErrorCode> {
+    let mut buffer: [u8; 34] = [0; 34]; // To be sure of the segment
+    let error_code: u16;
+    let country_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             inout("ax") 0x3800_u16 => error_code,
+             out("bx") country_code,
+             in("dx") buffer.as_mut_ptr() as u16);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    let currency = buffer[0x02..0x07].iter().take_while(|&&c| c != 0).map(|&c| c as char).collect();
+    Ok(CountryInfo {
+        country_code,
+        date_format: match buffer[0x00] {
+            1 => DateFormat::Europe,
+            2 => DateFormat::Japan,
+            _ => DateFormat::Usa,
+        },
+        currency,
+        thousands_separator: buffer[0x07] as char,
+        decimal_separator: buffer[0x09] as char,
+        date_separator: buffer[0x0B] as char,
+        time_separator: buffer[0x0D] as char,
+        currency_format: buffer[0x0F],
+        currency_digits: buffer[0x10],
+        time_format: if buffer[0x11] & 1 != 0 { TimeFormat::Hours24 } else { TimeFormat::Hours12 },
+        list_separator: buffer[0x16] as char,
+    })
+}
+
+/// Sets the current country (INT 21h AX=38FFh, DOS 3.0+). COUNTRY.SYS must have information about it.
+pub fn set_country(code: u16) -> Result<(), ErrorCode> {
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             inout("ax") 0x38FF_u16 => error_code,
+             in("bx") code,
+             in("dx") 0xFFFF_u16);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(())
+}
+
+/// Code pages, such as 437 for the original IBM PC character set or 850 for Latin-1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodePage {
+    /// Code page currently used by the devices
+    pub active: u16,
+    /// Code page the system was booted with
+    pub system: u16,
+}
+
+/// Gets the active and system code pages (INT 21h AX=6601h, DOS 3.30+).
+pub fn get_code_page() -> Result<CodePage, ErrorCode> {
+    if !dos_version_at_least(3, 30) {
+        return Err(ErrorCode::InvalidFunctionNumber);
+    }
+    let error_code: u16;
+    let active: u16;
+    let system: u16;
+    let carry: u8;
+    unsafe {
+        asm!("int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             inout("ax") 0x6601_u16 => error_code,
+             out("bx") active,
+             out("dx") system);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(CodePage { active, system })
+}
+
+/// Sets the active code page (INT 21h AX=6602h, DOS 3.30+). NLSFUNC must be loaded, and the
+/// devices prepared for the code page.
+pub fn set_code_page(code_page: u16) -> Result<(), ErrorCode> {
+    if !dos_version_at_least(3, 30) {
+        return Err(ErrorCode::InvalidFunctionNumber);
+    }
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             inout("ax") 0x6602_u16 => error_code,
+             in("bx") code_page);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(())
+}
+
+const fn identity_table() -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+static IDENTITY_TABLE: [u8; 256] = identity_table();
+static mut UPPERCASE_TABLE: [u8; 256] = [0; 256];
+static UPPERCASE_TABLE_READY: AtomicBool = AtomicBool::new(false);
+
+/// Gets a table of the current country (INT 21h AH=65h, DOS 3.30+), as the length prefixed
+/// table in DOS memory.
+fn country_table(info_id: u8) -> Option<&'static [u8]> {
+    if !dos_version_at_least(3, 30) {
+        return None;
+    }
+    let mut buffer: [u8; 5] = [0; 5]; // To be sure of the segment
+    let carry: u16;
+    unsafe {
+        asm!("push es", "push ds", "pop es", "int 0x21", "setc bl", "pop es",
+             inout("ax") 0x6500_u16 | info_id as u16 => _,
+             inout("bx") 0xFFFF_u16 => carry,
+             in("cx") buffer.len() as u16,
+             in("dx") 0xFFFF_u16,
+             in("di") buffer.as_mut_ptr() as u16);
+    }
+    if carry as u8 != 0 || buffer[0] != info_id {
+        return None;
+    }
+    let offset = u16::from_le_bytes([buffer[1], buffer[2]]);
+    let segment = u16::from_le_bytes([buffer[3], buffer[4]]);
+    let table = FarPtr::new(segment, offset).as_ptr::<u8>();
+    unsafe {
+        let length = u16::from_le_bytes([*table, *table.add(1)]);
+        Some(core::slice::from_raw_parts(table.add(2), length as usize))
+    }
+}
+
+/// Gets the uppercase table of the current country, mapping each character to its uppercase
+/// version. DOS only provides the characters above 127, the others are ASCII.
+///
+/// The table is read on the first call, later country changes are not taken into account.
+/// Before DOS 3.30, characters above 127 are left as is.
+pub fn get_uppercase_table() -> &'static [u8] {
+    if !UPPERCASE_TABLE_READY.load(Ordering::Acquire) {
+        let table = unsafe { &mut *addr_of_mut!(UPPERCASE_TABLE) };
+        for (i, entry) in table.iter_mut().enumerate() {
+            *entry = (i as u8).to_ascii_uppercase();
+        }
+        if let Some(dos_table) = country_table(0x02) {
+            let length = dos_table.len().min(128);
+            table[128..128 + length].copy_from_slice(&dos_table[..length]);
+        }
+        UPPERCASE_TABLE_READY.store(true, Ordering::Release);
+    }
+    unsafe { &*addr_of!(UPPERCASE_TABLE) }
+}
+
+/// Gets the collating sequence table of the current country, giving the sort weight of each
+/// character. Characters with the same weight, such as accented letters, compare as equal.
+///
+/// Before DOS 3.30, the weight of each character is its value.
+pub fn get_collate_table() -> &'static [u8] {
+    match country_table(0x06) {
+        Some(table) if table.len() == 256 => table,
+        _ => &IDENTITY_TABLE,
+    }
+}
+
+/// Ranges kept from the DOS table, more than any DBCS code page has.
+const MAX_DBCS_RANGES: usize = 16;
+
+static mut DBCS_RANGES: [(u8, u8); MAX_DBCS_RANGES] = [(0, 0); MAX_DBCS_RANGES];
+static mut DBCS_RANGE_COUNT: usize = 0;
+static DBCS_RANGES_READY: AtomicBool = AtomicBool::new(false);
+
+/// Gets the ranges of lead bytes of double-byte characters, such as Shift JIS, as inclusive (first, last) pairs
+/// (INT 21h AX=6300h). The table is empty when the code page has no double-byte characters,
+/// or before DOS 2.25. It is read on the first call.
+pub fn dbcs_lead_byte_table() -> &'static [(u8, u8)] {
+    if !DBCS_RANGES_READY.load(Ordering::Acquire) {
+        let status: u16;
+        let segment: u16;
+        let offset: u16;
+        unsafe {
+            // The table is returned in DS:SI, and SI can't be an operand
+            asm!("push ds", "push si", "xor si, si", "int 0x21",
+                 "mov {segment:x}, ds", "mov {offset:x}, si", "pop si", "pop ds",
+                 segment = out(reg) segment,
+                 offset = out(reg) offset,
+                 inout("ax") 0x6300_u16 => status);
+        }
+        let table = FarPtr::new(segment, offset);
+        let ranges = unsafe { &mut *addr_of_mut!(DBCS_RANGES) };
+        let mut count = 0;
+        // Unsupported versions leave AL or SI unchanged
+        if status as u8 == 0 && offset != 0 {
+            let bytes = table.as_ptr::<u8>();
+            while count < MAX_DBCS_RANGES {
+                let range = unsafe { (bytes.add(count * 2).read(), bytes.add(count * 2 + 1).read()) };
+                if range == (0, 0) {
+                    break;
+                }
+                ranges[count] = range;
+                count += 1;
+            }
+        }
+        unsafe { DBCS_RANGE_COUNT = count };
+        DBCS_RANGES_READY.store(true, Ordering::Release);
+    }
+    let (ranges, count) = unsafe { (&*addr_of!(DBCS_RANGES), DBCS_RANGE_COUNT) };
+    &ranges[..count]
+}
+
+/// Returns true if `byte` is the first byte of a double-byte character, so that a string must not be split after it.
+pub fn is_dbcs_lead_byte(byte: u8) -> bool {
+    dbcs_lead_byte_table().iter().any(|&(first, last)| (first..=last).contains(&byte))
+}
@@ -0,0 +1,191 @@
+//! Critical error handler (INT 24h), called by DOS when a device fails, instead of asking the user
+//! "Abort, Retry, Fail".
+//!
+//! The handler runs inside the failing DOS call: it may only call the console functions
+//! (INT 21h AH=01h to 0Ch) and AH=30h and 59h, and must not allocate.
+
+use core::ptr::{addr_of, addr_of_mut};
+use crate::dos::disk::DriveLetter;
+use crate::dos::error_code::ErrorCode;
+use crate::dos::far_ptr::FarPtr;
+use crate::dos::interrupt_vector::{get_interrupt_vector, set_interrupt_vector};
+
+/// Part of a drive being accessed when the error occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiskArea {
+    System,
+    Fat,
+    Directory,
+    Data,
+}
+
+/// Error passed to the handler, decoded from AX, DI and BP:SI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CriticalError {
+    /// Such as DiskWriteProtected, DriveNotReady or PrinterOutOfPaper
+    pub code: ErrorCode,
+    /// Drive that failed, or None for a character device such as the printer
+    pub drive: Option<DriveLetter>,
+    pub is_write: bool,
+    /// Only meaningful for drives
+    pub area: DiskArea,
+    pub can_fail: bool,
+    pub can_retry: bool,
+    pub can_ignore: bool,
+    /// Header of the device driver
+    pub device_header: FarPtr,
+}
+
+impl CriticalError {
+    fn from_registers(ax: u16, di: u16, bp: u16, si: u16) -> Self {
+        let flags = (ax >> 8) as u8;
+        let device_header = FarPtr::new(bp, si);
+        // Bit 7 is also set for a bad FAT in memory, so check the character device bit of the header instead
+        let device_attributes = unsafe { device_header.as_ptr::<u8>().add(4).cast::<u16>().read_unaligned() };
+        let is_character_device = flags & 0x80 != 0 && device_attributes & 0x8000 != 0;
+        CriticalError {
+            // Error codes 0 to 0Fh are the extended error codes from DiskWriteProtected (13h)
+            code: ErrorCode::from_u8((di as u8).wrapping_add(ErrorCode::DiskWriteProtected as u8))
+                .unwrap_or(ErrorCode::GeneralFailure),
+            drive: if is_character_device { None } else { Some(DriveLetter::from(ax as u8)) },
+            is_write: flags & 0x01 != 0,
+            area: match (flags >> 1) & 0x03 {
+                0 => DiskArea::System,
+                1 => DiskArea::Fat,
+                2 => DiskArea::Directory,
+                _ => DiskArea::Data,
+            },
+            can_fail: flags & 0x08 != 0,
+            can_retry: flags & 0x10 != 0,
+            can_ignore: flags & 0x20 != 0,
+            device_header,
+        }
+    }
+
+    /// Name of the character device, such as `PRN     `, or None for a drive.
+    pub fn device_name(&self) -> Option<[u8; 8]> {
+        self.drive.is_none().then(|| unsafe { self.device_header.as_ptr::<u8>().add(10).cast::<[u8; 8]>().read() })
+    }
+}
+
+/// What DOS does once the handler returns, given in AL.
+/// If the action isn't allowed, DOS changes it: Ignore to Fail, Retry to Fail, and Fail to Abort.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CriticalResponse {
+    /// Goes on as if the operation succeeded
+    Ignore = 0,
+    /// Tries the operation again
+    Retry = 1,
+    /// Terminates the program through INT 23h
+    Abort = 2,
+    /// Makes the DOS call fail with FailOnInterrupt24h (DOS 3.1+)
+    Fail = 3,
+}
+
+static mut CRITICAL_ERROR_HANDLER: Option<fn(CriticalError) -> CriticalResponse> = None;
+static mut OLD_CRITICAL_ERROR_VECTOR: FarPtr = FarPtr::new(0, 0);
+
+const STACK_SIZE: usize = 1024;
+static mut STACK: [u8; STACK_SIZE] = [0; STACK_SIZE];
+static mut SAVED_SS: u16 = 0;
+static mut SAVED_ESP: u32 = 0;
+static mut ERROR_AX: u16 = 0;
+static mut ERROR_DI: u16 = 0;
+static mut ERROR_BP: u16 = 0;
+static mut ERROR_SI: u16 = 0;
+static mut RESPONSE: u8 = 0;
+
+extern "C" fn call_critical_error_handler() {
+    unsafe {
+        let error = CriticalError::from_registers(addr_of!(ERROR_AX).read(), addr_of!(ERROR_DI).read(),
+                                                  addr_of!(ERROR_BP).read(), addr_of!(ERROR_SI).read());
+        let response = match addr_of!(CRITICAL_ERROR_HANDLER).read() {
+            Some(handler) => handler(error),
+            None => CriticalResponse::Fail,
+        };
+        addr_of_mut!(RESPONSE).write(response as u8);
+    }
+}
+
+/// Like the trampoline of [crate::interrupt_handler], but passes the registers DOS sets up to the handler
+/// and returns its response in AL. The stack frame of DOS is left untouched below the saved registers,
+/// as DOS unwinds it itself when the response is Abort or Fail.
+#[unsafe(naked)]
+unsafe extern "C" fn critical_error_trampoline() {
+    core::arch::naked_asm!(
+        "pushad", "push ds", "push es",
+        "mov cs:[{error_ax}], ax", "mov cs:[{error_di}], di",
+        "mov cs:[{error_bp}], bp", "mov cs:[{error_si}], si",
+        "mov ax, cs", "mov ds, ax", "mov es, ax",
+        "mov [{saved_ss}], ss", "mov [{saved_esp}], esp",
+        "mov ss, ax", "mov esp, offset {stack} + {stack_size}",
+        "cld",
+        "call {handler}",
+        "mov ss, [{saved_ss}]", "mov esp, [{saved_esp}]",
+        "pop es", "pop ds", "popad",
+        "mov al, cs:[{response}]",
+        "iret",
+        handler = sym call_critical_error_handler,
+        stack = sym STACK,
+        stack_size = const STACK_SIZE,
+        saved_ss = sym SAVED_SS,
+        saved_esp = sym SAVED_ESP,
+        error_ax = sym ERROR_AX,
+        error_di = sym ERROR_DI,
+        error_bp = sym ERROR_BP,
+        error_si = sym ERROR_SI,
+        response = sym RESPONSE,
+    );
+}
+
+/// Calls `handler` when a device fails, instead of asking the user (INT 24h).
+/// Installing again replaces the handler, keeping the original vector.
+/// DOS restores the original vector when the program terminates.
+///
+/// ```ignore
+/// install_critical_error_handler(|_| CriticalResponse::Fail);
+/// // Fails with FailOnInterrupt24h instead of asking, if A: is empty
+/// let file = File::open("A:\\DATA.TXT");
+/// ```
+pub fn install_critical_error_handler(handler: fn(CriticalError) -> CriticalResponse) {
+    unsafe {
+        if addr_of!(CRITICAL_ERROR_HANDLER).read().is_none() {
+            addr_of_mut!(OLD_CRITICAL_ERROR_VECTOR).write(get_interrupt_vector(0x24));
+        }
+        addr_of_mut!(CRITICAL_ERROR_HANDLER).write(Some(handler));
+    }
+    set_interrupt_vector(0x24, FarPtr::in_code_segment(critical_error_trampoline as *const () as usize as u16));
+}
+
+/// Restores the critical error handler replaced by [install_critical_error_handler].
+pub fn uninstall_critical_error_handler() {
+    unsafe {
+        if addr_of!(CRITICAL_ERROR_HANDLER).read().is_none() {
+            return;
+        }
+        set_interrupt_vector(0x24, addr_of!(OLD_CRITICAL_ERROR_VECTOR).read());
+        addr_of_mut!(CRITICAL_ERROR_HANDLER).write(None);
+    }
+}
+
+fn fail_critical_error(error: CriticalError) -> CriticalResponse {
+    // Fail isn't allowed before DOS 3.1, where it would become Abort
+    if error.can_fail {
+        CriticalResponse::Fail
+    } else {
+        CriticalResponse::Ignore
+    }
+}
+
+/// Runs `f` with critical errors failing the DOS call instead of asking the user,
+/// and then restores the handler and vector that were in place, even if they were installed by the user.
+pub(crate) fn without_critical_error_prompt<T>(f: impl FnOnce() -> T) -> T {
+    let handler = unsafe { addr_of!(CRITICAL_ERROR_HANDLER).read() };
+    let vector = get_interrupt_vector(0x24);
+    unsafe { addr_of_mut!(CRITICAL_ERROR_HANDLER).write(Some(fail_critical_error)) };
+    set_interrupt_vector(0x24, FarPtr::in_code_segment(critical_error_trampoline as *const () as usize as u16));
+    let result = f();
+    set_interrupt_vector(0x24, vector);
+    unsafe { addr_of_mut!(CRITICAL_ERROR_HANDLER).write(handler) };
+    result
+}
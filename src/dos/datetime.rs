@@ -0,0 +1,137 @@
+//! Dates and times, as used by DOS for the clock and file timestamps.
+
+use core::arch::asm;
+
+/// A calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: u16,
+    /// 1 to 12
+    pub month: u8,
+    /// 1 to 31
+    pub day: u8,
+}
+
+/// A time of the day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Time {
+    /// 0 to 23
+    pub hour: u8,
+    /// 0 to 59
+    pub minute: u8,
+    /// 0 to 59
+    pub second: u8,
+}
+
+impl Date {
+    pub const fn new(year: u16, month: u8, day: u8) -> Self {
+        Date { year, month, day }
+    }
+
+    /// Unpacks a DOS date word, as found in directory entries, FCBs and file timestamps:
+    /// bits 0-4 day, 5-8 month, 9-15 year - 1980.
+    pub const fn from_dos_word(word: u16) -> Self {
+        Date {
+            year: 1980 + (word >> 9),
+            month: ((word >> 5) & 0x0F) as u8,
+            day: (word & 0x1F) as u8,
+        }
+    }
+
+    /// Years outside of 1980 to 2107 are clamped.
+    pub const fn to_dos_word(&self) -> u16 {
+        let year = if self.year < 1980 { 0 } else if self.year > 2107 { 127 } else { self.year - 1980 };
+        (year << 9) | ((self.month as u16 & 0x0F) << 5) | (self.day as u16 & 0x1F)
+    }
+}
+
+impl Time {
+    pub const fn new(hour: u8, minute: u8, second: u8) -> Self {
+        Time { hour, minute, second }
+    }
+
+    /// Unpacks a DOS time word: bits 0-4 seconds / 2, 5-10 minutes, 11-15 hours.
+    pub const fn from_dos_word(word: u16) -> Self {
+        Time {
+            hour: (word >> 11) as u8,
+            minute: ((word >> 5) & 0x3F) as u8,
+            second: ((word & 0x1F) * 2) as u8,
+        }
+    }
+
+    /// Seconds are rounded down to an even number.
+    pub const fn to_dos_word(&self) -> u16 {
+        ((self.hour as u16 & 0x1F) << 11) | ((self.minute as u16 & 0x3F) << 5) | ((self.second as u16 / 2) & 0x1F)
+    }
+}
+
+/// A date and a time, read together by [Clock::now].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+}
+
+impl DateTime {
+    pub const fn new(date: Date, time: Time) -> Self {
+        DateTime { date, time }
+    }
+
+    /// Unpacks the date and time words of a file timestamp.
+    pub const fn from_dos_datetime_words(date: u16, time: u16) -> Self {
+        DateTime { date: Date::from_dos_word(date), time: Time::from_dos_word(time) }
+    }
+
+    /// Packs into (date, time) words, as stored in directory entries and by INT 21h AX=5701h.
+    pub const fn to_dos_datetime_words(&self) -> (u16, u16) {
+        (self.date.to_dos_word(), self.time.to_dos_word())
+    }
+
+    /// Seconds since midnight of the date, to measure durations within a day.
+    pub const fn total_seconds_since_midnight(&self) -> u32 {
+        self.time.hour as u32 * 3600 + self.time.minute as u32 * 60 + self.time.second as u32
+    }
+}
+
+/// The DOS system clock.
+pub struct Clock;
+
+impl Clock {
+    /// Reads the date and the time. As they are read with two calls, the date is read again after the time
+    /// until it didn't change, so that a read across midnight doesn't give the new time with the old date.
+    pub fn now() -> DateTime {
+        loop {
+            let date = get_date();
+            let time = get_time();
+            if get_date() == date {
+                return DateTime { date, time };
+            }
+        }
+    }
+}
+
+/// Gets the date of the system clock (INT 21h AH=2Ah). Prefer [Clock::now] when the time is also needed.
+pub fn get_date() -> Date {
+    let year: u16;
+    let month_and_day: u16;
+    unsafe {
+        asm!("int 0x21",
+             inout("ax") 0x2A00_u16 => _,
+             out("cx") year,
+             out("dx") month_and_day);
+    }
+    Date { year, month: (month_and_day >> 8) as u8, day: month_and_day as u8 }
+}
+
+/// Gets the time of the system clock, to the second (INT 21h AH=2Ch).
+pub fn get_time() -> Time {
+    let hour_and_minute: u16;
+    let second_and_hundredths: u16;
+    unsafe {
+        asm!("int 0x21",
+             inout("ax") 0x2C00_u16 => _,
+             out("cx") hour_and_minute,
+             out("dx") second_and_hundredths);
+    }
+    Time { hour: (hour_and_minute >> 8) as u8, minute: hour_and_minute as u8, second: (second_and_hundredths >> 8) as u8 }
+}
@@ -0,0 +1,96 @@
+//! Directory manipulation (INT 21h AH=39h, 3Ah, 3Bh and 47h) and path canonicalization (INT 21h AH=60h).
+
+use alloc::string::String;
+use core::arch::asm;
+use crate::dos::disk::DriveLetter;
+use crate::dos::error_code::ErrorCode;
+use crate::dos::file::to_path_array;
+use crate::dos::system::dos_version_at_least;
+
+/// Creates a directory.
+/// Fails with AccessDenied if the directory already exists.
+pub fn make_directory(path: &str) -> Result<(), ErrorCode> {
+    directory_function(0x39, path)
+}
+
+/// Removes an empty directory.
+/// Fails with AccessDenied if the directory is not empty.
+pub fn remove_directory(path: &str) -> Result<(), ErrorCode> {
+    directory_function(0x3A, path)
+}
+
+/// Changes the current directory.
+/// Fails with PathNotFound if the directory doesn't exist.
+pub fn change_directory(path: &str) -> Result<(), ErrorCode> {
+    directory_function(0x3B, path)
+}
+
+/// Gets the current directory of the given drive, as an absolute path such as `C:\GAMES`.
+pub fn current_directory(drive: DriveLetter) -> Result<String, ErrorCode> {
+    let drive_index = drive as u8;
+    let mut path_array: [u8; 64] = [0; 64]; // To be sure of the segment
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        // AH=47h numbers drives from 1 (A), 0 being the default drive
+        asm!("push si", "mov si, {path_ptr:x}", "mov ah, 0x47", "int 0x21", "setc {carry}", "pop si",
+             path_ptr = in(reg) path_array.as_mut_ptr() as u16,
+             carry = out(reg_byte) carry,
+             in("dl") drive_index + 1,
+             out("ax") error_code);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    // DOS returns the path without drive and leading backslash, and an empty string for the root
+    let mut current_directory = String::with_capacity(path_array.len() + 3);
+    current_directory.push((b'A' + drive_index) as char);
+    current_directory.push_str(":\\");
+    for &c in path_array.iter().take_while(|&&c| c != 0) {
+        current_directory.push(c as char);
+    }
+    Ok(current_directory)
+}
+
+/// Converts a path to a fully qualified one, with the drive and the absolute path, uppercased,
+/// and `.` and `..` resolved (INT 21h AH=60h, DOS 3.0+).
+/// The path doesn't have to exist, only the drive and the directories it goes through when resolving `..`.
+/// Fails with InvalidFunctionNumber before DOS 3.0.
+pub fn canonicalize(path: &str) -> Result<String, ErrorCode> {
+    if !dos_version_at_least(3, 0) {
+        return Err(ErrorCode::InvalidFunctionNumber);
+    }
+    let path_array = to_path_array(path)?;
+    let mut canonical_path_array: [u8; 128] = [0; 128]; // To be sure of the segment
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("push si", "push es", "push ds", "pop es",
+             "mov si, {path_ptr:x}", "mov ah, 0x60", "int 0x21", "setc {carry}",
+             "pop es", "pop si",
+             path_ptr = in(reg) path_array.as_c_str() as u16,
+             carry = out(reg_byte) carry,
+             in("di") canonical_path_array.as_mut_ptr() as u16,
+             out("ax") error_code);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(canonical_path_array.iter().take_while(|&&c| c != 0).map(|&c| c as char).collect())
+}
+
+fn directory_function(function: u8, path: &str) -> Result<(), ErrorCode> {
+    let path_array = to_path_array(path)?;
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             in("dx") path_array.as_c_str() as u16,
+             inout("ax") (function as u16) << 8 => error_code);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(())
+}
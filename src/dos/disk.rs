@@ -0,0 +1,546 @@
+//! Drive letters, disk capacity queries, Drive Parameter Blocks and absolute sector access (INT 25h/26h).
+
+use core::arch::asm;
+use core::cmp::min;
+use core::convert::TryFrom;
+use core::fmt::{self, Write};
+use crate::bios::disk::SECTOR_SIZE;
+use crate::dos::error_code::ErrorCode;
+use crate::dos::fcb::{close_extended_file, create_or_truncate_extended_file, rename_extended_file, ExtendedFcb, Fcb,
+                      FcbResult};
+use crate::dos::file::{find_first, FileAttributes};
+use crate::dos::String;
+use crate::dos::far_ptr::FarPtr;
+use crate::dos::critical_error::without_critical_error_prompt;
+use crate::dos::ioctl::get_logical_drive_map;
+use crate::dos::system::dos_version_at_least;
+
+/// A drive, numbered from 0 for A.
+/// Note that some DOS functions number drives from 1, 0 being the default drive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriveLetter {
+    A = 0,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Unknown,
+}
+
+impl From<u8> for DriveLetter {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::A,
+            1 => Self::B,
+            2 => Self::C,
+            3 => Self::D,
+            4 => Self::E,
+            5 => Self::F,
+            6 => Self::G,
+            7 => Self::H,
+            8 => Self::I,
+            9 => Self::J,
+            10 => Self::K,
+            11 => Self::L,
+            12 => Self::M,
+            13 => Self::N,
+            14 => Self::O,
+            15 => Self::P,
+            16 => Self::Q,
+            17 => Self::R,
+            18 => Self::S,
+            19 => Self::T,
+            20 => Self::U,
+            21 => Self::V,
+            22 => Self::W,
+            23 => Self::X,
+            24 => Self::Y,
+            25 => Self::Z,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl DriveLetter {
+    /// Like `From<u8>`, but fails with InvalidDrive for values above 25 instead of returning Unknown.
+    /// (`TryFrom<u8>` can't be implemented alongside `From<u8>`.)
+    pub fn try_from_u8(value: u8) -> Result<Self, ErrorCode> {
+        match Self::from(value) {
+            Self::Unknown => Err(ErrorCode::InvalidDrive),
+            drive => Ok(drive),
+        }
+    }
+
+    /// The letter of the drive, in uppercase, or None for Unknown.
+    pub fn as_char(&self) -> Option<char> {
+        match self {
+            Self::Unknown => None,
+            drive => Some((b'A' + *drive as u8) as char),
+        }
+    }
+}
+
+impl TryFrom<char> for DriveLetter {
+    type Error = ErrorCode;
+
+    /// Accepts `'A'..='Z'` and `'a'..='z'`.
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        if !value.is_ascii_alphabetic() {
+            return Err(ErrorCode::InvalidDrive);
+        }
+        Self::try_from_u8(value.to_ascii_uppercase() as u8 - b'A')
+    }
+}
+
+impl fmt::Display for DriveLetter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.as_char() {
+            Some(letter) => write!(f, "{}", letter),
+            None => write!(f, "?"),
+        }
+    }
+}
+
+/// Sets the default drive and returns the number of logical drives in the system (INT 21h AH=0Eh).
+///
+/// The count is the LASTDRIVE setting, or the number of drives if higher, so not every drive below it is valid.
+/// In the 512's DOS Plus (2.1) this call always returns five as the number of logical drives,
+/// though a maximum of four are supported.
+pub fn set_default_drive(drive: DriveLetter) -> u8 {
+    let drive_count: u8;
+    unsafe { asm!("int 0x21", in("ah") 0x0E_u8, in("dl") drive as u8, lateout("al") drive_count) }
+    drive_count
+}
+
+/// Gets the default drive (INT 21h AH=19h).
+pub fn get_default_drive() -> DriveLetter {
+    let drive: u8;
+    unsafe { asm!("int 0x21", in("ah") 0x19_u8, out("al") drive) }
+    DriveLetter::from(drive)
+}
+
+/// Gets the number of logical drives, by setting the default drive to itself.
+pub fn logical_drive_count() -> u8 {
+    set_default_drive(get_default_drive())
+}
+
+/// Iterator over the valid drives, as returned by [drives].
+pub struct DriveIter {
+    next: u8,
+    drive_count: u8,
+}
+
+impl Iterator for DriveIter {
+    type Item = DriveLetter;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.drive_count {
+            let drive = DriveLetter::from(self.next);
+            self.next += 1;
+            if is_drive_ready(drive) {
+                return Some(drive);
+            }
+        }
+        None
+    }
+}
+
+/// Returns true if the drive exists and has a disk in it.
+fn is_drive_ready(drive: DriveLetter) -> bool {
+    // B: of a single floppy machine would make DOS ask for its disk
+    if dos_version_at_least(3, 20) && matches!(get_logical_drive_map(drive), Ok(Some(mapped)) if mapped != drive) {
+        return false;
+    }
+    without_critical_error_prompt(|| get_allocation_info_for_specified_drive(drive).is_some())
+}
+
+/// Iterates over the drives from A: to the [logical_drive_count], skipping those that don't exist.
+///
+/// A drive that exists without a disk in it, such as an empty floppy drive, is skipped too:
+/// the critical error it raises is failed instead of asking the user "Abort, Retry, Fail".
+/// The second letter of a physical drive with two, such as B: on a machine with a single floppy drive,
+/// is also skipped when it isn't the current one, as DOS would ask the user to swap the disks.
+pub fn drives() -> DriveIter {
+    DriveIter { next: 0, drive_count: min(logical_drive_count(), DriveLetter::Unknown as u8) }
+}
+
+/// Capacity of a drive, as returned by [get_free_disk_space].
+pub struct DiskSpace {
+    pub free_bytes: u64,
+    pub total_bytes: u64,
+    pub bytes_per_cluster: u32,
+}
+
+/// Flushes all outstanding file buffers by physically updating to disc (INT 21h AH=0Dh).
+///
+/// This call does *not* update disc directories for any open files: if the files changed size, their
+/// directory entries are incorrect until they are closed. [crate::dos::file::flush_all_files] commits them.
+pub fn disk_reset() {
+    unsafe { asm!("int 0x21", in("ah") 0x0D_u8) }
+}
+
+/// Gets the free and total space of the given drive (INT 21h AH=36h).
+/// Returns None if the drive is invalid.
+pub fn get_free_disk_space(drive: DriveLetter) -> Option<DiskSpace> {
+    let sectors_per_cluster: u16;
+    let free_clusters: u16;
+    let bytes_per_sector: u16;
+    let total_clusters: u16;
+    // AH=36h numbers drives from 1 (A), 0 being the default drive
+    let drive_number = drive as u8 + 1;
+    unsafe {
+        asm!("mov ah, 0x36", "int 0x21",
+             inout("dx") drive_number as u16 => total_clusters,
+             out("ax") sectors_per_cluster,
+             out("bx") free_clusters,
+             out("cx") bytes_per_sector);
+    }
+    if sectors_per_cluster == 0xFFFF {
+        return None;
+    }
+    let bytes_per_cluster = sectors_per_cluster as u32 * bytes_per_sector as u32;
+    Some(DiskSpace {
+        free_bytes: free_clusters as u64 * bytes_per_cluster as u64,
+        total_bytes: total_clusters as u64 * bytes_per_cluster as u64,
+        bytes_per_cluster,
+    })
+}
+
+/// Drive allocation info, as returned by [get_allocation_info_for_default_drive] or [get_allocation_info_for_specified_drive].
+pub struct DriveAllocationInfo {
+    pub sectors_per_cluster: u8,
+    /// Pointer to the FAT information byte. To read the contents of the FAT into memory use [read_absolute_sectors].
+    /// See also [get_free_disk_space] which returns similar data.
+    pub fat_id_addr: *const u16,
+    /// Sector Size
+    pub sector_size: u16,
+    /// Number of clusters
+    pub number_of_clusters: u16,
+}
+
+impl DriveAllocationInfo {
+    pub fn bytes_per_cluster(&self) -> u32 {
+        self.sectors_per_cluster as u32 * self.sector_size as u32
+    }
+
+    /// Capacity of the drive, like [DiskSpace::total_bytes].
+    pub fn total_bytes(&self) -> u64 {
+        self.number_of_clusters as u64 * self.bytes_per_cluster() as u64
+    }
+}
+
+/// Obtains selected information about the current disk drive (INT 21h AH=1Bh).
+/// Returns None if the drive is invalid.
+pub fn get_allocation_info_for_default_drive() -> Option<DriveAllocationInfo> {
+    allocation_info_function(0x1B, 0)
+}
+
+/// Obtains selected information about the provided drive letter (INT 21h AH=1Ch).
+/// Returns None if the drive is invalid.
+pub fn get_allocation_info_for_specified_drive(drive_code: DriveLetter) -> Option<DriveAllocationInfo> {
+    // AH=1Ch numbers drives from 1 (A), 0 being the default drive
+    allocation_info_function(0x1C, drive_code as u8 + 1)
+}
+
+fn allocation_info_function(function: u8, drive_number: u8) -> Option<DriveAllocationInfo> {
+    let sectors_per_cluster: u16;
+    let fat_id_segment: u16;
+    let fat_id_offset: u16;
+    let sector_size: u16;
+    let number_of_clusters: u16;
+    unsafe {
+        // The FAT ID pointer is returned in DS:BX
+        asm!("push ds", "int 0x21", "mov {fat_id_segment:x}, ds", "pop ds",
+             fat_id_segment = out(reg) fat_id_segment,
+             inout("ax") (function as u16) << 8 => sectors_per_cluster,
+             out("bx") fat_id_offset,
+             out("cx") sector_size,
+             inout("dx") drive_number as u16 => number_of_clusters);
+    }
+    if sectors_per_cluster as u8 == 0xFF {
+        return None;
+    }
+    Some(DriveAllocationInfo {
+        sectors_per_cluster: sectors_per_cluster as u8,
+        fat_id_addr: FarPtr::new(fat_id_segment, fat_id_offset).as_ptr(),
+        sector_size,
+        number_of_clusters,
+    })
+}
+
+/// Drive Parameter Block, describing the layout of a FAT drive, as returned by
+/// [get_disk_parameter_block_for_default_drive] or [get_disk_parameter_block].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskParameterBlock {
+    pub drive: DriveLetter,
+    pub bytes_per_sector: u16,
+    pub sectors_per_cluster: u8,
+    /// Sectors before the first FAT, including the boot sector
+    pub reserved_sectors: u16,
+    pub fat_count: u8,
+    pub root_directory_entries: u16,
+    pub first_data_sector: u16,
+    pub cluster_count: u16,
+    pub sectors_per_fat: u16,
+    /// Same as the first byte of the FAT, such as F8h for a hard disk
+    pub media_id_byte: u8,
+}
+
+/// Gets the Drive Parameter Block of the default drive (INT 21h AH=1Fh).
+/// Returns None if the drive is invalid or isn't a FAT drive, such as a network drive.
+pub fn get_disk_parameter_block_for_default_drive() -> Option<DiskParameterBlock> {
+    disk_parameter_block_function(0x1F, 0)
+}
+
+/// Gets the Drive Parameter Block of a drive (INT 21h AH=32h, DOS 2.0+).
+/// Returns None if the drive is invalid or isn't a FAT drive, such as a network drive.
+/// DOS reads the boot sector if the disk may have changed, so this can trigger a critical error.
+pub fn get_disk_parameter_block(drive: DriveLetter) -> Option<DiskParameterBlock> {
+    // AH=32h numbers drives from 1 (A), 0 being the default drive
+    disk_parameter_block_function(0x32, drive as u8 + 1)
+}
+
+fn disk_parameter_block_function(function: u8, drive_number: u8) -> Option<DiskParameterBlock> {
+    let status: u16;
+    let dpb_segment: u16;
+    let dpb_offset: u16;
+    unsafe {
+        // The DPB pointer is returned in DS:BX
+        asm!("push ds", "int 0x21", "mov {dpb_segment:x}, ds", "pop ds",
+             dpb_segment = out(reg) dpb_segment,
+             inout("ax") (function as u16) << 8 => status,
+             out("bx") dpb_offset,
+             in("dl") drive_number);
+    }
+    if status as u8 == 0xFF {
+        return None;
+    }
+    let dpb: *const u8 = FarPtr::new(dpb_segment, dpb_offset).as_ptr();
+    let read_u8 = |offset: usize| unsafe { dpb.add(offset).read() };
+    let read_u16 = |offset: usize| unsafe { dpb.add(offset).cast::<u16>().read_unaligned() };
+    // DOS 4.0 widened the sectors per FAT to a word, moving the following fields by one byte
+    let (sectors_per_fat, media_id_offset) = if dos_version_at_least(4, 0) {
+        (read_u16(0x0F), 0x17)
+    } else {
+        (read_u8(0x0F) as u16, 0x16)
+    };
+    Some(DiskParameterBlock {
+        drive: DriveLetter::from(read_u8(0x00)),
+        bytes_per_sector: read_u16(0x02),
+        // Stored as the highest sector number in a cluster
+        sectors_per_cluster: read_u8(0x04) + 1,
+        reserved_sectors: read_u16(0x06),
+        fat_count: read_u8(0x08),
+        root_directory_entries: read_u16(0x09),
+        first_data_sector: read_u16(0x0B),
+        // Stored as the highest cluster number, clusters being numbered from 2
+        cluster_count: read_u16(0x0D).saturating_sub(1),
+        sectors_per_fat,
+        media_id_byte: read_u8(media_id_offset),
+    })
+}
+
+/// Identification of a disk, as stored in its boot sector, returned by [get_media_id].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MediaId {
+    /// Volume serial number, shown by DIR as XXXX-XXXX
+    pub serial: u32,
+    /// Padded with spaces. It may differ from the label in the root directory, which DIR shows.
+    pub label: [u8; 11],
+    /// Such as `FAT16   `, padded with spaces
+    pub fs_type: [u8; 8],
+}
+
+/// Buffer of INT 21h AH=69h.
+#[repr(C, packed)]
+struct MediaIdBlock {
+    info_level: u16,
+    serial: u32,
+    label: [u8; 11],
+    fs_type: [u8; 8],
+}
+
+fn media_id_function(subfunction: u8, drive: DriveLetter, block: &mut MediaIdBlock) -> Result<(), ErrorCode> {
+    if !dos_version_at_least(4, 0) {
+        return Err(ErrorCode::InvalidFunctionNumber);
+    }
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             inout("ax") 0x6900_u16 | subfunction as u16 => error_code,
+             // Numbered from 1 (A), 0 being the default drive
+             in("bx") drive as u16 + 1,
+             in("dx") block as *mut MediaIdBlock as u16);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(())
+}
+
+/// Gets the serial number, label and file system type of the disk in a drive (INT 21h AX=6900h, DOS 4.0+).
+/// Fails with InvalidFunctionNumber before DOS 4.0, or if the disk was formatted before DOS 4.0.
+pub fn get_media_id(drive: DriveLetter) -> Result<MediaId, ErrorCode> {
+    let mut block = MediaIdBlock { info_level: 0, serial: 0, label: [0; 11], fs_type: [0; 8] }; // To be sure of the segment
+    media_id_function(0x00, drive, &mut block)?;
+    Ok(MediaId { serial: block.serial, label: block.label, fs_type: block.fs_type })
+}
+
+/// Writes the serial number, label and file system type to the boot sector of the disk in a drive
+/// (INT 21h AX=6901h, DOS 4.0+).
+pub fn set_media_id(drive: DriveLetter, media_id: &MediaId) -> Result<(), ErrorCode> {
+    let mut block = MediaIdBlock { // To be sure of the segment
+        info_level: 0,
+        serial: media_id.serial,
+        label: media_id.label,
+        fs_type: media_id.fs_type,
+    };
+    media_id_function(0x01, drive, &mut block)
+}
+
+/// Gets the volume label of the disk in a drive, the special entry of its root directory shown by DIR and LABEL,
+/// or None if the disk has no label.
+pub fn volume_label(drive: DriveLetter) -> Result<Option<String>, ErrorCode> {
+    let mut path = String::new();
+    write!(path, "{}:\\*.*", drive).map_err(|_| ErrorCode::InvalidDrive)?;
+    let found = match find_first(&path, FileAttributes::VOLUME_LABEL) {
+        Ok(found) => found,
+        Err(ErrorCode::NoMoreFiles | ErrorCode::FileNotFound) => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    // Some versions of DOS also return files
+    if !found.attributes.contains(FileAttributes::VOLUME_LABEL) {
+        return Ok(None);
+    }
+    // Stored as a file name of 11 characters, which DOS returns with a dot after the 8th
+    let label = found.name.as_bytes().iter().enumerate()
+        .filter(|&(i, &c)| !(i == 8 && c == b'.'))
+        .map(|(_, &c)| c as char)
+        .collect();
+    Ok(Some(label))
+}
+
+/// Sets the volume label of the disk in a drive, replacing the existing one, as LABEL does
+/// with an extended FCB (INT 21h AH=17h, or AH=16h if the disk has no label).
+/// The label is converted to uppercase, and fails with InvalidData if it is longer than 11 characters or empty.
+pub fn set_volume_label(drive: DriveLetter, label: &str) -> Result<(), ErrorCode> {
+    if label.is_empty() || label.len() > 11 {
+        return Err(ErrorCode::InvalidData);
+    }
+    let mut new_name = Fcb::new();
+    new_name.drive = drive as u8 + 1;
+    for (i, c) in label.bytes().enumerate() {
+        let c = c.to_ascii_uppercase();
+        if i < 8 {
+            new_name.filename[i] = c;
+        } else {
+            new_name.extension[i - 8] = c;
+        }
+    }
+    let result = if volume_label(drive)?.is_some() {
+        let mut any_label = Fcb::new();
+        any_label.drive = drive as u8 + 1;
+        any_label.filename = [b'?'; 8];
+        any_label.extension = [b'?'; 3];
+        rename_extended_file(&ExtendedFcb::new(FileAttributes::VOLUME_LABEL, any_label), &new_name)
+    } else {
+        let mut fcb = ExtendedFcb::new(FileAttributes::VOLUME_LABEL, new_name);
+        match create_or_truncate_extended_file(&mut fcb) {
+            FcbResult::Success => close_extended_file(&mut fcb),
+            failure => failure,
+        }
+    };
+    match result {
+        FcbResult::Success => Ok(()),
+        // The root directory is full, or the disk is write protected
+        FcbResult::Failure => Err(ErrorCode::AccessDenied),
+    }
+}
+
+/// Parameters of INT 25h/26h with CX=FFFFh, for partitions above 32 MB.
+#[repr(C, packed)]
+struct AbsoluteDiskPacket {
+    start: u32,
+    count: u16,
+    buffer_offset: u16,
+    buffer_segment: u16,
+}
+
+fn absolute_function<const INTERRUPT: u8>(drive: DriveLetter, start: u32, count: u16, buffer: FarPtr) -> Result<(), ErrorCode> {
+    let packet;
+    let (target, cx, dx) = if dos_version_at_least(4, 0) {
+        packet = AbsoluteDiskPacket { start, count, buffer_offset: buffer.offset, buffer_segment: buffer.segment };
+        (FarPtr::from_ptr(&packet), 0xFFFF, 0)
+    } else {
+        // Before DOS 4.0 the sector number is 16 bits
+        if start > 0xFFFF {
+            return Err(ErrorCode::SectorNotFound);
+        }
+        (buffer, count, start as u16)
+    };
+    let error_code: u32;
+    let carry: u32;
+    unsafe {
+        // Every register but the segments may be changed, and the flags are left on the stack
+        asm!("push ebp", "push esi", "push ds", "mov ds, {segment:x}",
+             "int {interrupt}", "setc dl", "add sp, 2",
+             "pop ds", "pop esi", "pop ebp",
+             interrupt = const INTERRUPT,
+             segment = inout(reg) target.segment as u32 => _,
+             inout("eax") drive as u32 => error_code,
+             inout("ebx") target.offset as u32 => _,
+             inout("ecx") cx as u32 => _,
+             inout("edx") dx as u32 => carry);
+    }
+    if carry as u8 != 0 {
+        // AL is a critical error code, numbered like the DOS errors from DiskWriteProtected
+        return Err(ErrorCode::from_u8((error_code as u8).wrapping_add(ErrorCode::DiskWriteProtected as u8))
+            .unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(())
+}
+
+/// Reads `count` logical sectors of a drive from `start`, numbered from 0 for the boot sector (INT 25h).
+/// Sectors are assumed to be 512 bytes.
+pub fn read_absolute_sectors(drive: DriveLetter, start: u32, count: u16, buffer: &mut [u8]) -> Result<(), ErrorCode> {
+    if buffer.len() < count as usize * SECTOR_SIZE {
+        return Err(ErrorCode::InvalidData);
+    }
+    absolute_function::<0x25>(drive, start, count, FarPtr::from_ptr(buffer.as_mut_ptr()))
+}
+
+/// Writes `count` logical sectors of a drive from `start` (INT 26h). Sectors are assumed to be 512 bytes.
+///
+/// # Safety
+///
+/// **This overwrites the file system below DOS.** Writing the boot sector, the FAT or a directory
+/// with wrong data corrupts the drive and loses files. Only write sectors the program fully understands.
+pub unsafe fn write_absolute_sectors(drive: DriveLetter, start: u32, count: u16, buffer: &[u8]) -> Result<(), ErrorCode> {
+    if buffer.len() < count as usize * SECTOR_SIZE {
+        return Err(ErrorCode::InvalidData);
+    }
+    absolute_function::<0x26>(drive, start, count, FarPtr::from_ptr(buffer.as_ptr()))
+}
@@ -0,0 +1,138 @@
+//! Program arguments, from the command tail of the PSP, and environment variables.
+//! The command tail is copied at startup, since the default DTA shares its memory and DOS overwrites it
+//! on calls such as find first.
+
+use core::arch::asm;
+use core::ptr::addr_of;
+use crate::dos::far_ptr::FarPtr;
+use crate::psp::Psp;
+
+const COMMAND_TAIL_MAX_LEN: usize = 127;
+
+static mut PSP_SEGMENT: u16 = 0;
+static mut COMMAND_TAIL: [u8; COMMAND_TAIL_MAX_LEN] = [0; COMMAND_TAIL_MAX_LEN];
+static mut COMMAND_TAIL_LEN: usize = 0;
+
+/// Gets the segment of the PSP of the running program (INT 21h AH=62h, DOS 3.0+).
+fn get_psp_segment() -> u16 {
+    let segment: u16;
+    unsafe { asm!("mov ah, 0x62", "int 0x21", out("ax") _, out("bx") segment) }
+    segment
+}
+
+/// Saves the PSP segment and the command tail, called before main.
+pub(crate) fn init() {
+    let psp_segment = get_psp_segment();
+    let psp = Psp::new(psp_segment);
+    let tail = psp.command_tail();
+    unsafe {
+        for (i, &byte) in tail.iter().enumerate() {
+            // Non-ASCII characters depend on the code page, keep the tail valid UTF-8
+            COMMAND_TAIL[i] = if byte.is_ascii() { byte } else { b'?' };
+        }
+        COMMAND_TAIL_LEN = tail.len();
+        PSP_SEGMENT = psp_segment;
+    }
+}
+
+/// Segment of the PSP of the program, as saved at startup.
+pub fn psp_segment() -> u16 {
+    unsafe { PSP_SEGMENT }
+}
+
+/// Iterator over the program arguments, returned by [args].
+pub struct ArgsIter {
+    remaining: &'static str,
+}
+
+impl Iterator for ArgsIter {
+    type Item = &'static str;
+
+    fn next(&mut self) -> Option<&'static str> {
+        let is_separator = |c: char| c == ' ' || c == '\t';
+        let trimmed = self.remaining.trim_start_matches(is_separator);
+        if trimmed.is_empty() {
+            self.remaining = trimmed;
+            return None;
+        }
+        let end = trimmed.find(is_separator).unwrap_or(trimmed.len());
+        self.remaining = &trimmed[end..];
+        Some(&trimmed[..end])
+    }
+}
+
+/// Arguments given to the program, split on spaces and tabs. Unlike in C, the program name is not included.
+/// Non-ASCII characters are replaced by `?`.
+pub fn args() -> ArgsIter {
+    let (tail, len) = unsafe { (&*addr_of!(COMMAND_TAIL), COMMAND_TAIL_LEN) };
+    ArgsIter {
+        remaining: core::str::from_utf8(&tail[..len]).unwrap_or(""),
+    }
+}
+
+/// Iterator over the environment variables, returned by [environment].
+/// The block is usually below our data segment, so it is read through its own segment,
+/// keeping an offset in it rather than a pointer to compare with.
+pub struct EnvIter {
+    segment: u16,
+    offset: u16,
+}
+
+impl EnvIter {
+    // The environment block is at most 32 KB
+    const MAX_LEN: u16 = 0x8000;
+
+    fn byte_at(&self, offset: u16) -> u8 {
+        unsafe { FarPtr::new(self.segment, offset).as_ptr::<u8>().read_volatile() }
+    }
+
+    /// Reads the next ASCIIZ string, or None at the end of the block.
+    fn next_string(&mut self) -> Option<&'static [u8]> {
+        let start = self.offset;
+        let mut end = start;
+        while end < Self::MAX_LEN && self.byte_at(end) != 0 {
+            end += 1;
+        }
+        if end == start || end >= Self::MAX_LEN {
+            self.offset = Self::MAX_LEN;
+            return None;
+        }
+        self.offset = end + 1;
+        let string = FarPtr::new(self.segment, start).as_ptr::<u8>();
+        Some(unsafe { core::slice::from_raw_parts(string, (end - start) as usize) })
+    }
+}
+
+impl Iterator for EnvIter {
+    type Item = (&'static str, &'static str);
+
+    fn next(&mut self) -> Option<(&'static str, &'static str)> {
+        loop {
+            let string = self.next_string()?;
+            // Skip the strings which aren't variables
+            let string = match core::str::from_utf8(string) {
+                Ok(string) => string,
+                Err(_) => continue,
+            };
+            if let Some((name, value)) = string.split_once('=') {
+                return Some((name, value));
+            }
+        }
+    }
+}
+
+/// Environment variables of the program, as (name, value) pairs.
+/// The block ends with an empty string, so an empty environment yields nothing.
+pub fn environment() -> EnvIter {
+    EnvIter {
+        segment: Psp::new(psp_segment()).environment_segment(),
+        offset: 0,
+    }
+}
+
+/// Gets the value of an environment variable. Names are compared without case, as DOS stores them uppercase.
+pub fn getenv(name: &str) -> Option<&'static str> {
+    environment()
+        .find(|(variable_name, _)| variable_name.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value)
+}
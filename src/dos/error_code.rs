@@ -1,7 +1,10 @@
+use core::arch::asm;
 use core::convert::TryFrom;
 use core::fmt;
+use crate::bios::timer::sleep;
+use crate::dos::system::dos_version_at_least;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ErrorCode {
     Success = 0,
     InvalidFunctionNumber = 1,
@@ -174,6 +177,16 @@ impl ErrorCode {
         }
     }
 
+    /// True for the device errors that may go away by themselves or once the user acts,
+    /// such as a floppy drive door left open, retried by [with_retry].
+    /// FailOnInterrupt24h is included, as it is what these errors become when a critical error handler
+    /// answers Fail.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, ErrorCode::DriveNotReady | ErrorCode::ReadFault | ErrorCode::WriteFault
+            | ErrorCode::SeekError | ErrorCode::SectorNotFound | ErrorCode::DataErrorCrcFailed
+            | ErrorCode::InvalidDiskChange | ErrorCode::FailOnInterrupt24h)
+    }
+
     pub fn to_u8(&self) -> u8 {
         return *self as u8;
     }
@@ -284,4 +297,182 @@ impl TryFrom<u8> for ErrorCode {
             None => Err("Unknown error code"),
         }
     }
-}
\ No newline at end of file
+}
+
+/// Class of an extended error, as returned by [get_extended_error].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    OutOfResource = 1,
+    TemporarySituation = 2,
+    Authorization = 3,
+    Internal = 4,
+    HardwareFailure = 5,
+    SystemFailure = 6,
+    ApplicationError = 7,
+    NotFound = 8,
+    BadFormat = 9,
+    Locked = 10,
+    MediaError = 11,
+    AlreadyExists = 12,
+    Unknown = 13,
+}
+
+impl From<u8> for ErrorClass {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::OutOfResource,
+            2 => Self::TemporarySituation,
+            3 => Self::Authorization,
+            4 => Self::Internal,
+            5 => Self::HardwareFailure,
+            6 => Self::SystemFailure,
+            7 => Self::ApplicationError,
+            8 => Self::NotFound,
+            9 => Self::BadFormat,
+            10 => Self::Locked,
+            11 => Self::MediaError,
+            12 => Self::AlreadyExists,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Action DOS suggests to take after an extended error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecommendedAction {
+    Retry = 1,
+    DelayedRetry = 2,
+    PromptUser = 3,
+    AbortAfterCleanup = 4,
+    ImmediateAbort = 5,
+    Ignore = 6,
+    RetryAfterUserIntervention = 7,
+    Unknown,
+}
+
+impl From<u8> for RecommendedAction {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::Retry,
+            2 => Self::DelayedRetry,
+            3 => Self::PromptUser,
+            4 => Self::AbortAfterCleanup,
+            5 => Self::ImmediateAbort,
+            6 => Self::Ignore,
+            7 => Self::RetryAfterUserIntervention,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Where an extended error occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorLocus {
+    Unknown = 1,
+    BlockDevice = 2,
+    Network = 3,
+    SerialDevice = 4,
+    Memory = 5,
+}
+
+impl From<u8> for ErrorLocus {
+    fn from(value: u8) -> Self {
+        match value {
+            2 => Self::BlockDevice,
+            3 => Self::Network,
+            4 => Self::SerialDevice,
+            5 => Self::Memory,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Details about the last DOS error.
+#[derive(Debug, Clone, Copy)]
+pub struct ExtendedError {
+    pub code: u16,
+    pub class: ErrorClass,
+    pub action: RecommendedAction,
+    pub locus: ErrorLocus,
+}
+
+/// Gets details about the error of the last failed DOS call (INT 21h AH=59h).
+/// Must be called right after the failing call, since any other DOS call overwrites it.
+/// Returns None before DOS 3.0.
+pub fn get_extended_error() -> Option<ExtendedError> {
+    if !dos_version_at_least(3, 0) {
+        return None;
+    }
+    let code: u16;
+    let class_and_action: u16;
+    let locus: u16;
+    unsafe {
+        // CL, DX, SI, DI, BP, DS and ES are destroyed
+        asm!("push bp", "push si", "push ds", "push es",
+             "mov ah, 0x59", "int 0x21",
+             "pop es", "pop ds", "pop si", "pop bp",
+             inout("bx") 0_u16 => class_and_action,
+             out("ax") code,
+             out("cx") locus,
+             out("dx") _, out("di") _);
+    }
+    Some(ExtendedError {
+        code,
+        class: ErrorClass::from((class_and_action >> 8) as u8),
+        action: RecommendedAction::from(class_and_action as u8),
+        locus: ErrorLocus::from((locus >> 8) as u8),
+    })
+}
+
+/// An error code, along with the extended error information fetched when it was constructed.
+/// Convert right after the failing call, for instance with `File::open(path).map_err(DetailedError::from)`.
+#[derive(Debug, Clone, Copy)]
+pub struct DetailedError {
+    pub code: ErrorCode,
+    pub extended: Option<ExtendedError>,
+}
+
+impl From<ErrorCode> for DetailedError {
+    fn from(code: ErrorCode) -> Self {
+        DetailedError {
+            code,
+            extended: get_extended_error(),
+        }
+    }
+}
+
+impl fmt::Display for DetailedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.extended {
+            Some(extended) => write!(f, "{} ({:?}, {:?})", self.code, extended.class, extended.action),
+            None => write!(f, "{}", self.code),
+        }
+    }
+}
+
+/// Ticks waited by [with_retry] between attempts, about half a second.
+const RETRY_DELAY_TICKS: u32 = 9;
+
+/// Runs `operation` up to `attempts` times, at least once, while it fails with a [transient](ErrorCode::is_transient)
+/// error, waiting about half a second between attempts. Other errors are returned at once.
+///
+/// Without a critical error handler, DOS asks the user "Abort, Retry, Fail" before the operation even fails:
+/// install one answering Fail so that the errors reach this function.
+///
+/// ```ignore
+/// install_critical_error_handler(|_| CriticalResponse::Fail);
+/// let file = with_retry(5, || File::open("A:\\DATA.TXT"));
+/// uninstall_critical_error_handler();
+/// ```
+pub fn with_retry<T>(attempts: u32, mut operation: impl FnMut() -> Result<T, ErrorCode>) -> Result<T, ErrorCode> {
+    let mut attempt = 1;
+    loop {
+        match operation() {
+            Err(error) if error.is_transient() && attempt < attempts => {
+                sleep(RETRY_DELAY_TICKS);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Real mode segment:offset pointers.
+
+use core::arch::asm;
+
+/// A far pointer, as used by DOS to return addresses outside of our segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FarPtr {
+    pub segment: u16,
+    pub offset: u16,
+}
+
+impl FarPtr {
+    pub const fn new(segment: u16, offset: u16) -> Self {
+        FarPtr { segment, offset }
+    }
+
+    /// Far pointer to an offset in the code segment of this program, such as a function address.
+    pub fn in_code_segment(offset: u16) -> Self {
+        let code_segment: u16;
+        unsafe { asm!("mov {0:x}, cs", out(reg) code_segment) }
+        FarPtr::new(code_segment, offset)
+    }
+
+    /// Far pointer to a pointer of the program, normalized so that the offset is below 16.
+    /// Unlike a plain offset, it also reaches data outside of our segment, such as the heap.
+    pub fn from_ptr<T>(ptr: *const T) -> Self {
+        let data_segment: u16;
+        unsafe { asm!("mov {0:x}, ds", out(reg) data_segment) }
+        let linear_address = ((data_segment as u32) << 4).wrapping_add(ptr as u32);
+        FarPtr::new((linear_address >> 4) as u16, (linear_address & 0xF) as u16)
+    }
+
+    /// Linear address pointed to, (segment << 4) + offset.
+    pub const fn linear_address(&self) -> u32 {
+        ((self.segment as u32) << 4) + self.offset as u32
+    }
+
+    /// Pointer usable by the program. Pointers are offsets from our data segment,
+    /// so this is the linear address minus the base of DS.
+    pub fn as_ptr<T>(&self) -> *const T {
+        self.as_mut_ptr()
+    }
+
+    pub fn as_mut_ptr<T>(&self) -> *mut T {
+        let data_segment: u16;
+        unsafe { asm!("mov {0:x}, ds", out(reg) data_segment) }
+        self.linear_address().wrapping_sub((data_segment as u32) << 4) as *mut T
+    }
+
+    pub const fn is_null(&self) -> bool {
+        self.segment == 0 && self.offset == 0
+    }
+}
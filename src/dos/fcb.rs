@@ -0,0 +1,412 @@
+//! File Control Blocks, the CP/M style file records of DOS 1.
+//! Handles are easier to use, but FCBs are still used for parsing filenames and by some old programs.
+//! The FCB functions read and write records through the Disk Transfer Area, set by [set_disk_transfer_address].
+
+use core::arch::asm;
+use core::ops::BitOr;
+use crate::dos::datetime::{Date, Time};
+use crate::dos::error_code::ErrorCode;
+use crate::dos::far_ptr::FarPtr;
+use crate::dos::file::FileAttributes;
+
+/// A standard FCB, as filled by [parse_filename] and used by the FCB functions (INT 21h AH=0Fh to 24h).
+#[derive(Debug, Clone, Copy)]
+#[repr(C, packed)]
+pub struct Fcb {
+    /// 0 for the default drive, 1 for A, ...
+    pub drive: u8,
+    /// Padded with spaces
+    pub filename: [u8; 8],
+    /// Padded with spaces
+    pub extension: [u8; 3],
+    pub current_block: u16,
+    pub record_size: u16,
+    pub file_size: u32,
+    pub date: u16,
+    pub time: u16,
+    reserved: [u8; 8],
+    pub current_record: u8,
+    pub random_record: u32,
+}
+
+impl Fcb {
+    pub const fn new() -> Self {
+        Fcb {
+            drive: 0,
+            filename: [b' '; 8],
+            extension: [b' '; 3],
+            current_block: 0,
+            record_size: 0,
+            file_size: 0,
+            date: 0,
+            time: 0,
+            reserved: [0; 8],
+            current_record: 0,
+            random_record: 0,
+        }
+    }
+
+    /// The filename, without the padding spaces.
+    pub fn name(&self) -> &str {
+        trim_padding(&self.filename)
+    }
+
+    /// The extension, without the padding spaces.
+    pub fn extension(&self) -> &str {
+        trim_padding(&self.extension)
+    }
+
+    /// Date and time of the last modification, once the file is opened.
+    pub fn modified(&self) -> (Date, Time) {
+        (Date::from_dos_word(self.date), Time::from_dos_word(self.time))
+    }
+}
+
+impl Default for Fcb {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn trim_padding(field: &[u8]) -> &str {
+    core::str::from_utf8(field).unwrap_or("").trim_end_matches(' ')
+}
+
+/// Options of [parse_filename].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseFlags(u8);
+
+impl ParseFlags {
+    pub const NONE: ParseFlags = ParseFlags(0x00);
+    /// Skips leading separators such as spaces and `:;,=+`
+    pub const SKIP_LEADING_SEPARATORS: ParseFlags = ParseFlags(0x01);
+    /// Keeps the drive of the FCB if the input has none
+    pub const KEEP_DRIVE: ParseFlags = ParseFlags(0x02);
+    /// Keeps the filename of the FCB if the input has none
+    pub const KEEP_FILENAME: ParseFlags = ParseFlags(0x04);
+    /// Keeps the extension of the FCB if the input has none
+    pub const KEEP_EXTENSION: ParseFlags = ParseFlags(0x08);
+
+    pub const fn from_bits(bits: u8) -> Self {
+        ParseFlags(bits)
+    }
+
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn contains(&self, other: ParseFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for ParseFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        ParseFlags(self.0 | rhs.0)
+    }
+}
+
+/// Outcome of [parse_filename].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseResult {
+    NoWildcards,
+    /// The name contains `*` or `?`
+    Wildcards,
+    /// The drive letter doesn't match an existing drive
+    InvalidDrive,
+}
+
+/// Parses a filename such as `B:FOO.TXT` into an unopened FCB (INT 21h AH=29h).
+/// `*` is expanded to `?` up to the end of the field. Parsing stops at the first character not valid in a filename.
+/// Fails with InvalidData if the input is longer than 127 bytes.
+pub fn parse_filename(input: &str, flags: ParseFlags) -> Result<(Fcb, ParseResult), ErrorCode> {
+    let mut input_array: [u8; 128] = [0; 128]; // To be sure of the segment
+    if input.len() >= input_array.len() {
+        return Err(ErrorCode::InvalidData);
+    }
+    input_array[..input.len()].copy_from_slice(input.as_bytes());
+    input_array[input.len()] = b'\r';
+    let mut fcb = Fcb::new();
+    let result: u16;
+    unsafe {
+        asm!("push si", "push es", "push ds", "pop es",
+             "mov si, {input_ptr:x}", "mov ah, 0x29", "int 0x21",
+             "pop es", "pop si",
+             input_ptr = in(reg) input_array.as_ptr() as u16,
+             inout("ax") flags.bits() as u16 => result,
+             inout("di") &mut fcb as *mut Fcb as u16 => _);
+    }
+    let result = match result as u8 {
+        0x00 => ParseResult::NoWildcards,
+        0x01 => ParseResult::Wildcards,
+        _ => ParseResult::InvalidDrive,
+    };
+    Ok((fcb, result))
+}
+
+/// Sets the Disk Transfer Area, the buffer used by the FCB read and write functions and by find first (INT 21h AH=1Ah).
+/// DOS keeps the address, so the buffer must stay valid while it is used, and must be in our segment:
+/// a static or a local, not a heap allocation.
+pub fn set_disk_transfer_address(dta: &mut [u8]) {
+    unsafe {
+        asm!("mov ah, 0x1a", "int 0x21",
+             in("dx") dta.as_mut_ptr() as u16,
+             out("ax") _);
+    }
+}
+
+/// Gets the Disk Transfer Area (INT 21h AH=2Fh). At startup, it is in the PSP, over the command tail.
+pub fn get_disk_transfer_address() -> FarPtr {
+    let segment: u16;
+    let offset: u16;
+    unsafe {
+        asm!("push es", "mov ah, 0x2f", "int 0x21", "mov {segment:x}, es", "pop es",
+             segment = out(reg) segment,
+             out("ax") _,
+             out("bx") offset);
+    }
+    FarPtr::new(segment, offset)
+}
+
+/// Sets the Disk Transfer Area to any address, such as one returned by [get_disk_transfer_address] (INT 21h AH=1Ah).
+pub(crate) fn set_disk_transfer_far_address(dta: FarPtr) {
+    unsafe {
+        asm!("push ds", "mov ds, {segment:x}", "mov ah, 0x1a", "int 0x21", "pop ds",
+             segment = in(reg) dta.segment,
+             in("dx") dta.offset,
+             out("ax") _);
+    }
+}
+
+/// An FCB preceded by attributes, so that the FCB functions also find, create or rename hidden and system files,
+/// and the volume label.
+#[derive(Debug, Clone, Copy)]
+#[repr(C, packed)]
+pub(crate) struct ExtendedFcb {
+    /// FFh, to tell it from a standard FCB
+    flag: u8,
+    reserved: [u8; 5],
+    attributes: u8,
+    pub(crate) fcb: Fcb,
+}
+
+impl ExtendedFcb {
+    pub(crate) const fn new(attributes: FileAttributes, fcb: Fcb) -> Self {
+        ExtendedFcb { flag: 0xFF, reserved: [0; 5], attributes: attributes.bits(), fcb }
+    }
+}
+
+/// Calls an FCB function with a copy of the extended FCB on the stack, to be sure of the segment.
+fn extended_fcb_function(function: u8, fcb: &mut ExtendedFcb) -> FcbResult {
+    let mut stack_fcb = *fcb;
+    let status: u16;
+    unsafe {
+        asm!("int 0x21",
+             inout("ax") (function as u16) << 8 => status,
+             in("dx") &mut stack_fcb as *mut ExtendedFcb as u16);
+    }
+    *fcb = stack_fcb;
+    FcbResult::from_status(status as u8)
+}
+
+/// Like [create_or_truncate_file], with the attributes of the extended FCB (INT 21h AH=16h).
+pub(crate) fn create_or_truncate_extended_file(fcb: &mut ExtendedFcb) -> FcbResult {
+    extended_fcb_function(0x16, fcb)
+}
+
+/// Like [close_file], for a file created with [create_or_truncate_extended_file] (INT 21h AH=10h).
+pub(crate) fn close_extended_file(fcb: &mut ExtendedFcb) -> FcbResult {
+    extended_fcb_function(0x10, fcb)
+}
+
+/// Like [rename_file], for the files matching both the name and the attributes of `from` (INT 21h AH=17h).
+pub(crate) fn rename_extended_file(from: &ExtendedFcb, to: &Fcb) -> FcbResult {
+    let mut special_fcb = *from;
+    let mut renamed = special_fcb.fcb;
+    unsafe {
+        let bytes = &mut renamed as *mut Fcb as *mut u8;
+        core::ptr::copy_nonoverlapping(to.filename.as_ptr(), bytes.add(0x11), 8);
+        core::ptr::copy_nonoverlapping(to.extension.as_ptr(), bytes.add(0x19), 3);
+    }
+    special_fcb.fcb = renamed;
+    extended_fcb_function(0x17, &mut special_fcb)
+}
+
+/// Calls an FCB function with a copy of the FCB on the stack, to be sure of the segment.
+/// Returns AL and CX.
+fn fcb_function(function: u8, fcb: &mut Fcb, cx: u16) -> (u8, u16) {
+    let mut stack_fcb = *fcb;
+    let status: u16;
+    let cx_out: u16;
+    unsafe {
+        asm!("int 0x21",
+             inout("ax") (function as u16) << 8 => status,
+             inout("cx") cx => cx_out,
+             in("dx") &mut stack_fcb as *mut Fcb as u16);
+    }
+    *fcb = stack_fcb;
+    (status as u8, cx_out)
+}
+
+/// Status returned in AL by the FCB functions that don't transfer records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FcbResult {
+    Success,
+    /// The file wasn't found, or the disk or the directory is full
+    Failure,
+}
+
+impl FcbResult {
+    /// Status of the functions returning 0 on success and FFh on failure.
+    fn from_status(status: u8) -> Self {
+        match status {
+            0x00 => FcbResult::Success,
+            _ => FcbResult::Failure,
+        }
+    }
+}
+
+/// Status returned in AL by the FCB functions reading or writing records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FcbIoResult {
+    Success,
+    /// No record could be read, the end of the file was reached
+    Eof,
+    /// The last record of the file was read, and padded with zeros
+    PartialRecord,
+    /// The record couldn't be written
+    DiskFull,
+    /// The record would go past the end of the segment of the DTA
+    DtaTooSmall,
+    /// A status the function isn't documented to return, treated as a failure
+    Unknown(u8),
+}
+
+impl FcbIoResult {
+    /// Status of the read functions: 0 success, 1 end of file, 2 DTA too small, 3 partial record.
+    fn from_read_status(status: u8) -> Self {
+        match status {
+            0x00 => FcbIoResult::Success,
+            0x01 => FcbIoResult::Eof,
+            0x02 => FcbIoResult::DtaTooSmall,
+            0x03 => FcbIoResult::PartialRecord,
+            status => FcbIoResult::Unknown(status),
+        }
+    }
+
+    /// Status of the write functions: 0 success, 1 disk full, 2 DTA too small.
+    fn from_write_status(status: u8) -> Self {
+        match status {
+            0x00 => FcbIoResult::Success,
+            0x01 => FcbIoResult::DiskFull,
+            0x02 => FcbIoResult::DtaTooSmall,
+            status => FcbIoResult::Unknown(status),
+        }
+    }
+}
+
+/// Opens the file described by an unopened FCB, such as one filled by [parse_filename] (INT 21h AH=0Fh).
+/// On success, the record size is set to 128 and can be changed before reading or writing.
+pub fn open_file(fcb: &mut Fcb) -> FcbResult {
+    FcbResult::from_status(fcb_function(0x0F, fcb, 0).0)
+}
+
+/// Closes a file opened with [open_file] (INT 21h AH=10h).
+pub fn close_file(fcb: &mut Fcb) -> FcbResult {
+    FcbResult::from_status(fcb_function(0x10, fcb, 0).0)
+}
+
+/// Finds the first file matching an unopened FCB, wildcards allowed (INT 21h AH=11h).
+/// On success, the DTA receives an unopened FCB of the file found.
+pub fn find_first_file(fcb: &mut Fcb) -> FcbResult {
+    FcbResult::from_status(fcb_function(0x11, fcb, 0).0)
+}
+
+/// Finds the next file matching the FCB given to [find_first_file] (INT 21h AH=12h).
+pub fn find_next_file(fcb: &mut Fcb) -> FcbResult {
+    FcbResult::from_status(fcb_function(0x12, fcb, 0).0)
+}
+
+/// Deletes the files matching an unopened FCB, wildcards allowed (INT 21h AH=13h).
+pub fn delete_file(fcb: &mut Fcb) -> FcbResult {
+    FcbResult::from_status(fcb_function(0x13, fcb, 0).0)
+}
+
+/// Reads the record at the current block and record of the FCB into the DTA, and moves to the next one (INT 21h AH=14h).
+pub fn sequential_read(fcb: &mut Fcb) -> FcbIoResult {
+    FcbIoResult::from_read_status(fcb_function(0x14, fcb, 0).0)
+}
+
+/// Writes the record in the DTA at the current block and record of the FCB, and moves to the next one (INT 21h AH=15h).
+pub fn sequential_write(fcb: &mut Fcb) -> FcbIoResult {
+    FcbIoResult::from_write_status(fcb_function(0x15, fcb, 0).0)
+}
+
+/// Creates the file described by an unopened FCB, or truncates it if it exists, and opens it (INT 21h AH=16h).
+pub fn create_or_truncate_file(fcb: &mut Fcb) -> FcbResult {
+    FcbResult::from_status(fcb_function(0x16, fcb, 0).0)
+}
+
+/// Renames the files matching `from` to the name of `to`, wildcards allowed in both (INT 21h AH=17h).
+/// The drive of `to` is ignored.
+pub fn rename_file(from: &Fcb, to: &Fcb) -> FcbResult {
+    // The new name goes at offset 11h of the old FCB
+    let mut special_fcb = *from;
+    unsafe {
+        let bytes = &mut special_fcb as *mut Fcb as *mut u8;
+        core::ptr::copy_nonoverlapping(to.filename.as_ptr(), bytes.add(0x11), 8);
+        core::ptr::copy_nonoverlapping(to.extension.as_ptr(), bytes.add(0x19), 3);
+    }
+    FcbResult::from_status(fcb_function(0x17, &mut special_fcb, 0).0)
+}
+
+/// Reads the record at the random record of the FCB into the DTA (INT 21h AH=21h).
+/// Unlike [random_block_read], the random record isn't moved, but the current block and record are set to it.
+pub fn random_read(fcb: &mut Fcb) -> FcbIoResult {
+    FcbIoResult::from_read_status(fcb_function(0x21, fcb, 0).0)
+}
+
+/// Writes the record in the DTA at the random record of the FCB (INT 21h AH=22h).
+/// Unlike [random_block_write], the random record isn't moved, but the current block and record are set to it.
+pub fn random_write(fcb: &mut Fcb) -> FcbIoResult {
+    FcbIoResult::from_write_status(fcb_function(0x22, fcb, 0).0)
+}
+
+/// Gets the size of the file described by an unopened FCB, in records of the FCB record size,
+/// rounded up (INT 21h AH=23h). The record size must be set first.
+/// The random record of the FCB is also set to the size. Returns None if the file isn't found.
+pub fn get_file_size_in_records(fcb: &mut Fcb) -> Option<u32> {
+    if fcb_function(0x23, fcb, 0).0 != 0x00 {
+        return None;
+    }
+    Some(random_record(fcb))
+}
+
+/// Sets the random record of the FCB to its current block and record (INT 21h AH=24h),
+/// to switch from sequential to random access.
+pub fn set_random_record_number(fcb: &mut Fcb) {
+    fcb_function(0x24, fcb, 0);
+}
+
+/// Random record of the FCB, which only has 3 bytes for records of 64 bytes or more.
+fn random_record(fcb: &Fcb) -> u32 {
+    if fcb.record_size >= 64 { fcb.random_record & 0x00FF_FFFF } else { fcb.random_record }
+}
+
+/// Reads `count` records into the DTA, starting at the random record of the FCB (INT 21h AH=27h).
+/// The random record is then moved past the records read.
+/// Returns the status and the number of records read, including a partial last record.
+pub fn random_block_read(fcb: &mut Fcb, count: u16) -> (FcbIoResult, u16) {
+    let (status, records_read) = fcb_function(0x27, fcb, count);
+    (FcbIoResult::from_read_status(status), records_read)
+}
+
+/// Writes `count` records from the DTA, starting at the random record of the FCB (INT 21h AH=28h).
+/// With a count of 0, the file is truncated or extended to the random record instead.
+/// Returns the status and the number of records written.
+pub fn random_block_write(fcb: &mut Fcb, count: u16) -> (FcbIoResult, u16) {
+    let (status, records_written) = fcb_function(0x28, fcb, count);
+    (FcbIoResult::from_write_status(status), records_written)
+}
@@ -1,9 +1,31 @@
 use core::arch::asm;
 use core::cmp::min;
+use core::fmt;
+use core::mem::ManuallyDrop;
+use core::ops::BitOr;
+use crate::dos::datetime::{Date, Time};
+use crate::dos::disk::disk_reset;
 use crate::dos::error_code::ErrorCode;
+use crate::dos::output::Output;
+use crate::dos::fcb::{get_disk_transfer_address, set_disk_transfer_address, set_disk_transfer_far_address};
+use crate::dos::{vec, String, Vec};
+use crate::dos::system::dos_version_at_least;
+use crate::util::ArrayString;
 
 extern crate rlibc;
 
+/// Handles opened by DOS for every program.
+pub const STDIN: u16 = 0;
+pub const STDOUT: u16 = 1;
+pub const STDERR: u16 = 2;
+/// Auxiliary device, usually COM1
+pub const STDAUX: u16 = 3;
+/// Printer, usually LPT1
+pub const STDPRN: u16 = 4;
+
+/// Bytes transferred by each DOS call of the buffered functions.
+const CHUNK_SIZE: usize = 512;
+
 #[allow(dead_code)]
 pub struct File {
     handle: u16,
@@ -33,22 +55,172 @@ impl SeekFrom {
     }
 }
 
+/// Options to open a file with, choosing between the DOS open and create functions.
+///
+/// ```ignore
+/// let log = OpenOptions::new().append(true).create(true).open("PROGRAM.LOG")?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OpenOptions {
+    read: bool,
+    write: bool,
+    append: bool,
+    create: bool,
+    truncate: bool,
+    create_new: bool,
+}
+
+impl OpenOptions {
+    /// All options are off: at least read, write or append must be set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read(&mut self, read: bool) -> &mut Self {
+        self.read = read;
+        self
+    }
+
+    pub fn write(&mut self, write: bool) -> &mut Self {
+        self.write = write;
+        self
+    }
+
+    /// Writes at the end of the file. DOS has no append mode: the file is positioned at its end once opened,
+    /// so seeking elsewhere then writing doesn't append.
+    pub fn append(&mut self, append: bool) -> &mut Self {
+        self.append = append;
+        self
+    }
+
+    /// Creates the file if it doesn't exist. Needs write or append.
+    pub fn create(&mut self, create: bool) -> &mut Self {
+        self.create = create;
+        self
+    }
+
+    /// Empties the file if it exists. Needs write, and not append.
+    pub fn truncate(&mut self, truncate: bool) -> &mut Self {
+        self.truncate = truncate;
+        self
+    }
+
+    /// Creates the file, failing with FileAlreadyExists if it exists (DOS 3.0+). Needs write or append,
+    /// and ignores create and truncate.
+    pub fn create_new(&mut self, create_new: bool) -> &mut Self {
+        self.create_new = create_new;
+        self
+    }
+
+    /// Opens the file (INT 21h AH=3Dh), or creates it (AH=3Ch, or AH=5Bh for create_new).
+    /// Fails with InvalidAccessCode if the options conflict. Created files are always readable too.
+    pub fn open(&self, path: &str) -> Result<File, ErrorCode> {
+        let writes = self.write || self.append;
+        if (!self.read && !writes)
+            || ((self.create || self.create_new) && !writes)
+            || (self.truncate && (!self.write || self.append)) {
+            return Err(ErrorCode::InvalidAccessCode);
+        }
+        // Denying nothing to other programs, like File::open
+        let mode = 0x40 | match (self.read, writes) {
+            (true, false) => 0,
+            (false, true) => 1,
+            _ => 2,
+        };
+        let file = if self.create_new {
+            File::create_new(path, FileAttributes::NORMAL)?
+        } else if self.create && self.truncate {
+            File::create(path)?
+        } else {
+            match File::open_function(path, mode) {
+                Err(ErrorCode::FileNotFound) if self.create => File::create(path)?,
+                Ok(file) if self.truncate => {
+                    // Writing 0 bytes truncates at the position
+                    unsafe { file.write_in_segment(&[])? };
+                    file
+                }
+                result => result?,
+            }
+        };
+        if self.append {
+            file.seek(SeekFrom::End(0))?;
+        }
+        Ok(file)
+    }
+}
+
 #[allow(dead_code)]
 #[allow(unused_assignments)]
 impl File {
+    /// One of the handles opened by DOS for every program, such as [STDOUT], not closed when dropped.
+    pub(crate) fn standard(handle: u16) -> ManuallyDrop<File> {
+        ManuallyDrop::new(File { handle })
+    }
+
+    /// Opens a file for reading, denying nothing to other programs (INT 21h AH=3Dh).
     pub fn open(filename: &str) -> Result<Self, ErrorCode> {
-        let mut is_open_success: u16 = 1; // 0: success, 1: fail
-        let mut error_code_or_handle: u16 = 0;
-        // DOS PATH length limit is 66 bytes.
-        let mut filename_array: [u8; 70] = [0; 70]; // To be sure of the segment
-        for i in 0..min(filename_array.len(), filename.len()) {
-            filename_array[i] = filename.as_bytes()[i];
-        }
-        let filename_ptr = filename_array.as_ptr();
+        Self::open_function(filename, 0x40)
+    }
+
+    /// Creates a file, or truncates it if it exists, and opens it for reading and writing (INT 21h AH=3Ch).
+    pub fn create(filename: &str) -> Result<Self, ErrorCode> {
+        Self::create_function(0x3C, filename, FileAttributes::NORMAL)
+    }
+
+    /// Creates a file and opens it for reading and writing, failing with FileAlreadyExists if it exists
+    /// (INT 21h AH=5Bh, DOS 3.0+). Checking and creating are a single operation, suitable for lock files.
+    pub fn create_new(filename: &str, attributes: FileAttributes) -> Result<Self, ErrorCode> {
+        if !dos_version_at_least(3, 0) {
+            return Err(ErrorCode::InvalidFunctionNumber);
+        }
+        Self::create_function(0x5B, filename, attributes)
+    }
+
+    /// Creates a file with a unique name in a directory, and opens it for reading and writing
+    /// (INT 21h AH=5Ah, DOS 3.0+). Returns the file with its full path.
+    /// An empty directory is the current one. The file isn't deleted when closed.
+    /// Fails with PathNotFound if the directory is too long for DOS.
+    pub fn create_temp(directory: &str) -> Result<(Self, String), ErrorCode> {
+        if !dos_version_at_least(3, 0) {
+            return Err(ErrorCode::InvalidFunctionNumber);
+        }
+        let directory = to_path_array(directory)?;
+        // Room for the backslash, then for the name DOS appends: 13 bytes with its terminator.
+        let mut path_array: [u8; 70 + 13] = [0; 70 + 13]; // To be sure of the segment
+        let mut length = directory.len();
+        path_array[..length].copy_from_slice(directory.as_bytes());
+        if length > 0 && !matches!(path_array[length - 1], b'\\' | b':') {
+            path_array[length] = b'\\';
+            length += 1;
+        }
+        let error_code_or_handle: u16;
+        let carry: u8;
         unsafe {
-            asm!("mov al, 0x40", "mov ah, 0x3d", "int 0x21", "setc  dl", "movzx cx, dl", in("dx") filename_ptr as u16, lateout("cx") is_open_success, lateout("ax") error_code_or_handle);
+            asm!("mov ah, 0x5a", "int 0x21", "setc {carry}",
+                 carry = out(reg_byte) carry,
+                 in("cx") FileAttributes::NORMAL.bits() as u16,
+                 in("dx") path_array.as_mut_ptr() as u16,
+                 out("ax") error_code_or_handle);
         }
-        if is_open_success == 1 {
+        if carry != 0 {
+            return Err(ErrorCode::from_u8(error_code_or_handle as u8).unwrap_or(ErrorCode::UnknownError));
+        }
+        let path = path_array.iter().take_while(|&&c| c != 0).map(|&c| c as char).collect();
+        Ok((Self { handle: error_code_or_handle }, path))
+    }
+
+    /// Opens a file with an access and sharing mode (INT 21h AH=3Dh): 0 for reading, 1 for writing, 2 for both.
+    fn open_function(filename: &str, mode: u8) -> Result<Self, ErrorCode> {
+        let filename_array = to_path_array(filename)?;
+        let error_code_or_handle: u16;
+        let carry: u8;
+        unsafe {
+            asm!("int 0x21", "setc {carry}",
+                 carry = out(reg_byte) carry,
+                 inout("ax") 0x3D00_u16 | mode as u16 => error_code_or_handle,
+                 in("dx") filename_array.as_c_str() as u16);
+        }
+        if carry != 0 {
             return Err(ErrorCode::from_u8(error_code_or_handle as u8).unwrap_or(ErrorCode::UnknownError));
         }
         Ok(Self {
@@ -56,6 +228,29 @@ impl File {
         })
     }
 
+    fn create_function(function: u8, filename: &str, attributes: FileAttributes) -> Result<Self, ErrorCode> {
+        let filename_array = to_path_array(filename)?;
+        let error_code_or_handle: u16;
+        let carry: u8;
+        unsafe {
+            asm!("int 0x21", "setc {carry}",
+                 carry = out(reg_byte) carry,
+                 inout("ax") (function as u16) << 8 => error_code_or_handle,
+                 in("cx") attributes.bits() as u16,
+                 in("dx") filename_array.as_c_str() as u16);
+        }
+        if carry != 0 {
+            return Err(ErrorCode::from_u8(error_code_or_handle as u8).unwrap_or(ErrorCode::UnknownError));
+        }
+        Ok(Self {
+            handle: error_code_or_handle,
+        })
+    }
+
+    pub fn handle(&self) -> u16 {
+        self.handle
+    }
+
     pub fn read(&self, buffer: &mut [u8]) -> Result<usize, ErrorCode> {
         let mut total_bytes_read: usize = 0;
         for buffer_write_pos in 0..buffer.len() {
@@ -87,6 +282,70 @@ impl File {
         Ok(total_bytes_read)
     }
 
+    /// Reads up to CHUNK_SIZE bytes with a single call (INT 21h AH=3Fh). Returns 0 at the end of the file.
+    fn read_chunk(&self, buffer: &mut [u8]) -> Result<usize, ErrorCode> {
+        let mut chunk: [u8; CHUNK_SIZE] = [0; CHUNK_SIZE]; // To be sure of the segment
+        let length = min(chunk.len(), buffer.len());
+        let bytes_read = unsafe { self.read_in_segment(&mut chunk[..length])? };
+        buffer[..bytes_read].copy_from_slice(&chunk[..bytes_read]);
+        Ok(bytes_read)
+    }
+
+    /// Reads with a single call (INT 21h AH=3Fh). Returns 0 at the end of the file.
+    ///
+    /// # Safety
+    /// The buffer must be in our data segment, such as a local: DOS gets its offset only.
+    unsafe fn read_in_segment(&self, buffer: &mut [u8]) -> Result<usize, ErrorCode> {
+        let error_code_or_bytes_read: u16;
+        let carry: u16;
+        unsafe {
+            // No register left for the carry, BL receives it
+            asm!("mov ah, 0x3f", "int 0x21", "setc bl",
+                 inout("bx") self.handle => carry,
+                 in("cx") buffer.len() as u16,
+                 in("dx") buffer.as_mut_ptr() as u16,
+                 out("ax") error_code_or_bytes_read);
+        }
+        if carry as u8 != 0 {
+            return Err(ErrorCode::from_u8(error_code_or_bytes_read as u8).unwrap_or(ErrorCode::UnknownError));
+        }
+        Ok(error_code_or_bytes_read as usize)
+    }
+
+    /// Writes up to CHUNK_SIZE bytes with a single call (INT 21h AH=40h).
+    /// A short write, when the disk is full, fails with InsufficientDiskSpace.
+    fn write_chunk(&self, buffer: &[u8]) -> Result<usize, ErrorCode> {
+        let mut chunk: [u8; CHUNK_SIZE] = [0; CHUNK_SIZE]; // To be sure of the segment
+        let length = min(chunk.len(), buffer.len());
+        chunk[..length].copy_from_slice(&buffer[..length]);
+        unsafe { self.write_in_segment(&chunk[..length]) }
+    }
+
+    /// Writes with a single call (INT 21h AH=40h).
+    /// A short write, when the disk is full, fails with InsufficientDiskSpace.
+    ///
+    /// # Safety
+    /// The buffer must be in our data segment, such as a local: DOS gets its offset only.
+    unsafe fn write_in_segment(&self, buffer: &[u8]) -> Result<usize, ErrorCode> {
+        let error_code_or_bytes_written: u16;
+        let carry: u16;
+        unsafe {
+            // No register left for the carry, BL receives it
+            asm!("mov ah, 0x40", "int 0x21", "setc bl",
+                 inout("bx") self.handle => carry,
+                 in("cx") buffer.len() as u16,
+                 in("dx") buffer.as_ptr() as u16,
+                 out("ax") error_code_or_bytes_written);
+        }
+        if carry as u8 != 0 {
+            return Err(ErrorCode::from_u8(error_code_or_bytes_written as u8).unwrap_or(ErrorCode::UnknownError));
+        }
+        if (error_code_or_bytes_written as usize) < buffer.len() {
+            return Err(ErrorCode::InsufficientDiskSpace);
+        }
+        Ok(buffer.len())
+    }
+
     // TODO check
     pub fn write(&self, buffer: &[u8]) -> Result<usize, ErrorCode> {
         let mut total_bytes_written: usize = 0;
@@ -110,6 +369,30 @@ impl File {
         Ok(total_bytes_written)
     }
 
+    /// Writes the whole buffer, with as many calls as needed.
+    /// Fails with InsufficientDiskSpace if DOS writes fewer bytes than requested, when the disk is full.
+    pub fn write_all(&mut self, mut buffer: &[u8]) -> Result<(), ErrorCode> {
+        while !buffer.is_empty() {
+            let bytes_written = self.write_chunk(buffer)?;
+            buffer = &buffer[bytes_written..];
+        }
+        Ok(())
+    }
+
+    /// Fills the whole buffer, with as many calls as needed.
+    /// Fails with CannotCompleteFileOperation, "handle EOF" for DOS, if the end of the file is reached first:
+    /// the content of the buffer is then unspecified.
+    pub fn read_exact(&mut self, mut buffer: &mut [u8]) -> Result<(), ErrorCode> {
+        while !buffer.is_empty() {
+            let bytes_read = self.read_chunk(buffer)?;
+            if bytes_read == 0 {
+                return Err(ErrorCode::CannotCompleteFileOperation);
+            }
+            buffer = &mut buffer[bytes_read..];
+        }
+        Ok(())
+    }
+
     pub fn close(self) -> Result<(), ErrorCode> {
         self.close_with_ref()
     }
@@ -148,6 +431,717 @@ impl File {
         }
         Ok((new_pos_high_from_start as u32) << 16 | (error_code_or_new_pos_low_from_start as u32))
     }
+
+    /// Locks a region of the file, so that other programs can't access it (INT 21h AX=5C00h, DOS 3.0+).
+    /// Locking a region overlapping a locked one fails, with LockViolation (or AccessDenied on some versions).
+    /// SHARE.EXE must be loaded, otherwise it fails with InvalidFunctionNumber, as it does before DOS 3.0.
+    pub fn lock(&self, offset: u32, length: u32) -> Result<(), ErrorCode> {
+        self.lock_function(0x5C00, offset, length)
+    }
+
+    /// Unlocks a region locked with [File::lock], with the same offset and length (INT 21h AX=5C01h, DOS 3.0+).
+    pub fn unlock(&self, offset: u32, length: u32) -> Result<(), ErrorCode> {
+        self.lock_function(0x5C01, offset, length)
+    }
+
+    fn lock_function(&self, function: u16, offset: u32, length: u32) -> Result<(), ErrorCode> {
+        if !dos_version_at_least(3, 0) {
+            return Err(ErrorCode::InvalidFunctionNumber);
+        }
+        let error_code: u16;
+        let carry: u16;
+        unsafe {
+            // SI can't be an operand: the length is given in EDI, and its high half moved to SI
+            asm!("push esi", "mov esi, edi", "shr esi, 16", "int 0x21", "setc bl", "pop esi",
+                 inout("ax") function => error_code,
+                 inout("bx") self.handle => carry,
+                 in("cx") (offset >> 16) as u16,
+                 in("dx") offset as u16,
+                 in("edi") length);
+        }
+        if carry as u8 != 0 {
+            return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+        }
+        Ok(())
+    }
+
+    /// Gets the date and time of the last write to the file (INT 21h AX=5700h).
+    pub fn modified(&self) -> Result<(Date, Time), ErrorCode> {
+        let error_code: u16;
+        let time: u16;
+        let date: u16;
+        let carry: u16;
+        unsafe {
+            // No register left for the carry, BL receives it
+            asm!("int 0x21", "setc bl",
+                 inout("ax") 0x5700_u16 => error_code,
+                 inout("bx") self.handle => carry,
+                 out("cx") time,
+                 out("dx") date);
+        }
+        if carry as u8 != 0 {
+            return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+        }
+        Ok((Date::from_dos_word(date), Time::from_dos_word(time)))
+    }
+
+    /// Sets the date and time of the last write to the file (INT 21h AX=5701h).
+    /// Seconds are stored with a 2 seconds precision.
+    pub fn set_modified(&self, date: Date, time: Time) -> Result<(), ErrorCode> {
+        let error_code: u16;
+        let carry: u16;
+        unsafe {
+            // No register left for the carry, BL receives it
+            asm!("int 0x21", "setc bl",
+                 inout("ax") 0x5701_u16 => error_code,
+                 inout("bx") self.handle => carry,
+                 in("cx") time.to_dos_word(),
+                 in("dx") date.to_dos_word());
+        }
+        if carry as u8 != 0 {
+            return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+        }
+        Ok(())
+    }
+
+    /// Writes the buffers of the file to the disk and updates its directory entry, without closing it
+    /// (INT 21h AH=68h, DOS 3.30+). Unlike a disk reset (AH=0Dh), the file size on disk is made current.
+    pub fn commit(&self) -> Result<(), ErrorCode> {
+        if !dos_version_at_least(3, 30) {
+            return Err(ErrorCode::InvalidFunctionNumber);
+        }
+        let error_code: u16;
+        let carry: u8;
+        unsafe {
+            asm!("mov ah, 0x68", "int 0x21", "setc {carry}",
+                 carry = out(reg_byte) carry,
+                 in("bx") self.handle,
+                 out("ax") error_code);
+        }
+        if carry != 0 {
+            return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+        }
+        Ok(())
+    }
+}
+
+/// Attribute byte of a directory entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileAttributes(u8);
+
+impl FileAttributes {
+    pub const NORMAL: FileAttributes = FileAttributes(0x00);
+    pub const READ_ONLY: FileAttributes = FileAttributes(0x01);
+    pub const HIDDEN: FileAttributes = FileAttributes(0x02);
+    pub const SYSTEM: FileAttributes = FileAttributes(0x04);
+    pub const VOLUME_LABEL: FileAttributes = FileAttributes(0x08);
+    pub const DIRECTORY: FileAttributes = FileAttributes(0x10);
+    pub const ARCHIVE: FileAttributes = FileAttributes(0x20);
+
+    pub const fn from_bits(bits: u8) -> Self {
+        FileAttributes(bits)
+    }
+
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn contains(&self, other: FileAttributes) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn remove(&self, other: FileAttributes) -> Self {
+        FileAttributes(self.0 & !other.0)
+    }
+}
+
+impl BitOr for FileAttributes {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        FileAttributes(self.0 | rhs.0)
+    }
+}
+
+/// Gets the attributes of a file or directory (INT 21h AX=4300h).
+pub fn get_attributes(path: &str) -> Result<FileAttributes, ErrorCode> {
+    let path_array = to_path_array(path)?;
+    let error_code: u16;
+    let attributes: u16;
+    let carry: u8;
+    unsafe {
+        asm!("int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             in("dx") path_array.as_c_str() as u16,
+             inout("ax") 0x4300_u16 => error_code,
+             out("cx") attributes);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(FileAttributes::from_bits(attributes as u8))
+}
+
+/// Sets the attributes of a file (INT 21h AX=4301h).
+/// The volume label and directory bits can't be set this way, and are rejected with InvalidData.
+pub fn set_attributes(path: &str, attributes: FileAttributes) -> Result<(), ErrorCode> {
+    if attributes.contains(FileAttributes::VOLUME_LABEL) || attributes.contains(FileAttributes::DIRECTORY) {
+        return Err(ErrorCode::InvalidData);
+    }
+    let path_array = to_path_array(path)?;
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             in("dx") path_array.as_c_str() as u16,
+             in("cx") attributes.bits() as u16,
+             inout("ax") 0x4301_u16 => error_code);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(())
+}
+
+/// Renames or moves a file or directory (INT 21h AH=56h). Files can be moved to another directory,
+/// directories only renamed. Fails with NotSameDevice if the paths are on different drives,
+/// and AccessDenied if the new name exists.
+pub fn rename(from: &str, to: &str) -> Result<(), ErrorCode> {
+    let from_array = to_path_array(from)?;
+    let to_array = to_path_array(to)?;
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("push es", "push ds", "pop es", "mov ah, 0x56", "int 0x21", "setc {carry}", "pop es",
+             carry = out(reg_byte) carry,
+             in("dx") from_array.as_c_str() as u16,
+             in("di") to_array.as_c_str() as u16,
+             out("ax") error_code);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(())
+}
+
+/// Deletes a file (INT 21h AH=41h). Fails with AccessDenied if the file is read-only or is a directory.
+pub fn remove_file(path: &str) -> Result<(), ErrorCode> {
+    let path_array = to_path_array(path)?;
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("mov ah, 0x41", "int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             in("dx") path_array.as_c_str() as u16,
+             out("ax") error_code);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(())
+}
+
+/// Bytes transferred by each DOS call of [copy].
+const COPY_BUFFER_SIZE: usize = 8192;
+
+/// Copies a file, creating or truncating the destination, and gives it the modification time of the source.
+/// Returns the number of bytes copied. Fails with InsufficientDiskSpace when the disk is full,
+/// leaving the destination partially written.
+pub fn copy(from: &str, to: &str) -> Result<u32, ErrorCode> {
+    let source = File::open(from)?;
+    let destination = File::create(to)?;
+    let mut buffer: [u8; COPY_BUFFER_SIZE] = [0; COPY_BUFFER_SIZE]; // To be sure of the segment
+    let mut total_bytes_copied: u32 = 0;
+    loop {
+        let bytes_read = unsafe { source.read_in_segment(&mut buffer)? };
+        if bytes_read == 0 {
+            break;
+        }
+        unsafe { destination.write_in_segment(&buffer[..bytes_read])? };
+        total_bytes_copied += bytes_read as u32;
+    }
+    // After the last write, else closing sets the current time
+    let (date, time) = source.modified()?;
+    destination.set_modified(date, time)?;
+    destination.close()?;
+    Ok(total_bytes_copied)
+}
+
+/// Reads a whole file.
+pub fn read(path: &str) -> Result<Vec<u8>, ErrorCode> {
+    let mut file = File::open(path)?;
+    let size = file.seek(SeekFrom::End(0))?;
+    file.seek(SeekFrom::Start(0))?;
+    let mut contents = vec![0; size as usize];
+    file.read_exact(&mut contents)?;
+    file.close()?;
+    Ok(contents)
+}
+
+/// The characters of the bytes 80h to FFh in code page 437, the code page of most DOS text files.
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+/// Reads a whole text file. A valid UTF-8 file is read as UTF-8, any other as code page 437,
+/// so that accented and box drawing characters come out as themselves (C9h gives '╔').
+/// The few CP437 texts that are valid UTF-8 too, such as C3h A9h, are read as UTF-8.
+pub fn read_to_string(path: &str) -> Result<String, ErrorCode> {
+    let contents = read(path)?;
+    match String::from_utf8(contents) {
+        Ok(text) => Ok(text),
+        Err(error) => Ok(error.into_bytes().iter().map(|&byte| match byte {
+            0x00..=0x7f => byte as char,
+            _ => CP437_HIGH[byte as usize - 0x80],
+        }).collect()),
+    }
+}
+
+/// Writes a whole file, creating it or truncating it if it exists.
+/// Fails with InsufficientDiskSpace when the disk is full, leaving the file partially written.
+pub fn write(path: &str, contents: &[u8]) -> Result<(), ErrorCode> {
+    let mut file = File::create(path)?;
+    file.write_all(contents)?;
+    file.close()
+}
+
+/// Writes a whole text file, like [write].
+pub fn write_str(path: &str, contents: &str) -> Result<(), ErrorCode> {
+    write(path, contents.as_bytes())
+}
+
+/// Gets the size of a file from its directory entry, without opening it.
+/// Fails with FileNotFound if the path has wildcards or doesn't exist, as [metadata], and AccessDenied if it is a directory.
+pub fn file_size(path: &str) -> Result<u32, ErrorCode> {
+    let metadata = metadata(path)?;
+    if metadata.is_dir {
+        return Err(ErrorCode::AccessDenied);
+    }
+    Ok(metadata.size)
+}
+
+/// Information about a file or directory, as returned by [metadata].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metadata {
+    /// 0 for a directory
+    pub size: u32,
+    pub attributes: FileAttributes,
+    pub modified: (Date, Time),
+    pub is_dir: bool,
+}
+
+/// Gets the information about a file or directory from its directory entry, without opening it.
+/// Fails with FileNotFound if the path has wildcards or doesn't exist, even where DOS reports NoMoreFiles.
+/// The root directory of a drive has no entry, and isn't found.
+pub fn metadata(path: &str) -> Result<Metadata, ErrorCode> {
+    if path.contains(['*', '?']) {
+        return Err(ErrorCode::FileNotFound);
+    }
+    let attributes = FileAttributes::HIDDEN | FileAttributes::SYSTEM | FileAttributes::DIRECTORY;
+    let found = find_first(path, attributes).map_err(|error| match error {
+        // What find first returns when nothing matches, while there is no search here
+        ErrorCode::NoMoreFiles => ErrorCode::FileNotFound,
+        error => error,
+    })?;
+    Ok(Metadata {
+        size: found.size,
+        attributes: found.attributes,
+        modified: (found.date, found.time),
+        is_dir: found.attributes.contains(FileAttributes::DIRECTORY),
+    })
+}
+
+/// A file found by [find_first], as DOS describes it in the DTA.
+pub(crate) struct FoundFile {
+    /// As NAME.EXT, without the padding spaces
+    pub(crate) name: ArrayString<13>,
+    pub(crate) attributes: FileAttributes,
+    pub(crate) date: Date,
+    pub(crate) time: Time,
+    pub(crate) size: u32,
+}
+
+/// Finds the first file matching a path, wildcards allowed (INT 21h AH=4Eh).
+/// Normal files are always found, and the hidden, system and directory attributes add those files.
+/// The DTA is set to a local buffer during the call, and then restored.
+pub(crate) fn find_first(path: &str, attributes: FileAttributes) -> Result<FoundFile, ErrorCode> {
+    let path_array = to_path_array(path)?;
+    let mut dta: [u8; 43] = [0; 43]; // To be sure of the segment
+    let original_dta = get_disk_transfer_address();
+    set_disk_transfer_address(&mut dta);
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("mov ah, 0x4e", "int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             in("cx") attributes.bits() as u16,
+             in("dx") path_array.as_c_str() as u16,
+             out("ax") error_code);
+    }
+    set_disk_transfer_far_address(original_dta);
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    let mut name = ArrayString::new();
+    for &c in dta[0x1E..].iter().take_while(|&&c| c != 0) {
+        let _ = name.push(c as char);
+    }
+    Ok(FoundFile {
+        name,
+        attributes: FileAttributes::from_bits(dta[0x15]),
+        time: Time::from_dos_word(u16::from_le_bytes([dta[0x16], dta[0x17]])),
+        date: Date::from_dos_word(u16::from_le_bytes([dta[0x18], dta[0x19]])),
+        size: u32::from_le_bytes([dta[0x1A], dta[0x1B], dta[0x1C], dta[0x1D]]),
+    })
+}
+
+/// NUL-terminated path. DOS PATH length limit is 66 bytes, plus the drive and the terminator.
+pub(crate) type PathString = ArrayString<70>;
+
+/// Copies a path into a NUL-terminated stack buffer.
+/// Fails with PathNotFound, as DOS does, if the path is too long.
+pub(crate) fn to_path_array(path: &str) -> Result<PathString, ErrorCode> {
+    let mut path_array = PathString::new(); // To be sure of the segment
+    path_array.push_str(path).map_err(|_| ErrorCode::PathNotFound)?;
+    Ok(path_array)
+}
+
+/// Duplicates a handle: the new handle refers to the same file, and shares its position (INT 21h AH=45h).
+pub fn duplicate_handle(handle: u16) -> Result<u16, ErrorCode> {
+    let error_code_or_handle: u16;
+    let carry: u8;
+    unsafe {
+        asm!("mov ah, 0x45", "int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             in("bx") handle,
+             out("ax") error_code_or_handle);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code_or_handle as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(error_code_or_handle)
+}
+
+/// Makes `existing` refer to the same file as `target`, closing what `existing` referred to first (INT 21h AH=46h).
+pub fn redirect_handle(existing: u16, target: u16) -> Result<(), ErrorCode> {
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("mov ah, 0x46", "int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             in("bx") target,
+             in("cx") existing,
+             out("ax") error_code);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(())
+}
+
+/// Sets the maximum number of handles the program can open, 20 by default (INT 21h AH=67h, DOS 3.3+).
+/// The table is allocated from the free DOS memory when `count` is above 20.
+/// The `FILES=` line of CONFIG.SYS still limits the number of files open in the whole system.
+/// Fails with InvalidFunctionNumber before DOS 3.3.
+pub fn set_handle_count(count: u16) -> Result<(), ErrorCode> {
+    if !dos_version_at_least(3, 30) {
+        return Err(ErrorCode::InvalidFunctionNumber);
+    }
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("mov ah, 0x67", "int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             in("bx") count,
+             out("ax") error_code);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(())
+}
+
+/// Commits the given files with [File::commit], so that their directory entries are up to date, then flushes
+/// every other buffer with [disk_reset]. DOS doesn't give the list of handles a program has open, hence the slice.
+/// All files are committed even if one fails, and the first error is returned.
+pub fn flush_all_files(files: &[&File]) -> Result<(), ErrorCode> {
+    let mut result = Ok(());
+    for file in files {
+        if let Err(error) = file.commit() {
+            result = result.and(Err(error));
+        }
+    }
+    disk_reset();
+    result
+}
+
+/// Redirects the standard output to a file, until dropped.
+///
+/// ```ignore
+/// let file = File::create("OUTPUT.TXT")?;
+/// let redirect = StdioRedirect::new(&file)?;
+/// println!("Written to OUTPUT.TXT");
+/// drop(redirect);
+/// ```
+pub struct StdioRedirect {
+    saved_stdout: File,
+}
+
+impl StdioRedirect {
+    pub fn new(target: &File) -> Result<Self, ErrorCode> {
+        let saved_stdout = File {
+            handle: duplicate_handle(STDOUT)?,
+        };
+        redirect_handle(STDOUT, target.handle)?;
+        Ok(StdioRedirect { saved_stdout })
+    }
+}
+
+impl Drop for StdioRedirect {
+    fn drop(&mut self) {
+        // The duplicate is closed when saved_stdout is dropped
+        let _ = redirect_handle(STDOUT, self.saved_stdout.handle);
+    }
+}
+
+/// Redirects the standard input from a file, until dropped, like `<` in the shell.
+/// Child processes started meanwhile inherit the redirection.
+///
+/// ```ignore
+/// let redirect = StdinRedirect::new("INPUT.TXT")?;
+/// let line = stdin().read_line();
+/// drop(redirect);
+/// ```
+pub struct StdinRedirect {
+    saved_stdin: File,
+}
+
+impl StdinRedirect {
+    /// Opens the file for reading. If it can't be opened, the standard input is left untouched.
+    pub fn new(path: &str) -> Result<Self, ErrorCode> {
+        // Closed at the end, the standard input keeps the file open
+        let file = File::open(path)?;
+        let saved_stdin = File {
+            handle: duplicate_handle(STDIN)?,
+        };
+        redirect_handle(STDIN, file.handle)?;
+        Ok(StdinRedirect { saved_stdin })
+    }
+}
+
+impl Drop for StdinRedirect {
+    fn drop(&mut self) {
+        // The duplicate is closed when saved_stdin is dropped
+        let _ = redirect_handle(STDIN, self.saved_stdin.handle);
+    }
+}
+
+/// Reads the standard input until its end, appending to `buffer`, and returns the number of bytes read.
+/// For filters such as `TYPE FILE.TXT | PROGRAM` or `PROGRAM < FILE.TXT`.
+///
+/// From the keyboard, the input is read line by line and ends with Ctrl-Z then Enter, but only if the console
+/// is in cooked mode: in raw mode, set with [crate::dos::ioctl::set_raw_mode], Ctrl-Z is an ordinary byte
+/// and this never returns.
+pub fn read_stdin_to_end(buffer: &mut Vec<u8>) -> Result<usize, ErrorCode> {
+    let stdin = File::standard(STDIN);
+    let mut chunk: [u8; CHUNK_SIZE] = [0; CHUNK_SIZE]; // To be sure of the segment
+    let mut total_bytes_read = 0;
+    loop {
+        let bytes_read = unsafe { stdin.read_in_segment(&mut chunk)? };
+        if bytes_read == 0 {
+            return Ok(total_bytes_read);
+        }
+        buffer.extend_from_slice(&chunk[..bytes_read]);
+        total_bytes_read += bytes_read;
+    }
+}
+
+/// Reads a file through a buffer, filled by chunks of 512 bytes instead of one DOS call per byte.
+///
+/// ```ignore
+/// let reader = BufReader::new(File::open("CONFIG.SYS")?);
+/// for line in reader.lines() {
+///     println!("{}", line?);
+/// }
+/// ```
+pub struct BufReader {
+    file: File,
+    buffer: [u8; CHUNK_SIZE],
+    position: usize,
+    filled: usize,
+}
+
+impl BufReader {
+    pub fn new(file: File) -> Self {
+        BufReader {
+            file,
+            buffer: [0; CHUNK_SIZE],
+            position: 0,
+            filled: 0,
+        }
+    }
+
+    /// Returns the file. Data already buffered is lost.
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+
+    /// Refills the buffer if it was consumed. Returns false at the end of the file.
+    fn fill_buffer(&mut self) -> Result<bool, ErrorCode> {
+        if self.position == self.filled {
+            self.filled = self.file.read_chunk(&mut self.buffer)?;
+            self.position = 0;
+        }
+        Ok(self.filled != 0)
+    }
+
+    /// Reads a byte, or returns None at the end of the file.
+    pub fn read_byte(&mut self) -> Result<Option<u8>, ErrorCode> {
+        if !self.fill_buffer()? {
+            return Ok(None);
+        }
+        let byte = self.buffer[self.position];
+        self.position += 1;
+        Ok(Some(byte))
+    }
+
+    /// Appends bytes to `buffer` until `delimiter` (included) or the end of the file.
+    /// Returns the number of bytes appended, 0 at the end of the file.
+    pub fn read_until(&mut self, delimiter: u8, buffer: &mut Vec<u8>) -> Result<usize, ErrorCode> {
+        let mut total_bytes_read = 0;
+        while self.fill_buffer()? {
+            let available = &self.buffer[self.position..self.filled];
+            let (length, found) = match available.iter().position(|&c| c == delimiter) {
+                Some(index) => (index + 1, true),
+                None => (available.len(), false),
+            };
+            buffer.extend_from_slice(&available[..length]);
+            self.position += length;
+            total_bytes_read += length;
+            if found {
+                break;
+            }
+        }
+        Ok(total_bytes_read)
+    }
+
+    /// Iterates over the lines of the file, without their CRLF or LF ending.
+    /// Bytes above 127 are converted as if the file was Latin-1.
+    pub fn lines(self) -> Lines {
+        Lines { reader: self }
+    }
+}
+
+/// Iterator over the lines of a file, created by [BufReader::lines].
+pub struct Lines {
+    reader: BufReader,
+}
+
+impl Iterator for Lines {
+    type Item = Result<String, ErrorCode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = Vec::new();
+        match self.reader.read_until(b'\n', &mut line) {
+            Ok(0) => None,
+            Ok(_) => {
+                if line.last() == Some(&b'\n') {
+                    line.pop();
+                    if line.last() == Some(&b'\r') {
+                        line.pop();
+                    }
+                }
+                Some(Ok(line.iter().map(|&c| c as char).collect()))
+            }
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
+
+/// Writes a file through a buffer, written by chunks of 512 bytes instead of one DOS call per byte.
+/// The buffer is written when full, by [BufWriter::flush], and when dropped, ignoring errors:
+/// [BufWriter::into_inner] reports them.
+///
+/// ```ignore
+/// let mut writer = BufWriter::new(File::create("OUTPUT.TXT")?);
+/// writeln!(writer, "{} lines", count)?;
+/// let file = writer.into_inner()?;
+/// ```
+pub struct BufWriter {
+    // None once returned by into_inner
+    file: Option<File>,
+    buffer: [u8; CHUNK_SIZE],
+    filled: usize,
+}
+
+impl BufWriter {
+    pub fn new(file: File) -> Self {
+        BufWriter {
+            file: Some(file),
+            buffer: [0; CHUNK_SIZE],
+            filled: 0,
+        }
+    }
+
+    /// Buffers all of `data`, writing the buffer each time it is full.
+    pub fn write(&mut self, data: &[u8]) -> Result<usize, ErrorCode> {
+        let mut remaining = data;
+        while !remaining.is_empty() {
+            if self.filled == self.buffer.len() {
+                self.flush()?;
+            }
+            let length = min(self.buffer.len() - self.filled, remaining.len());
+            self.buffer[self.filled..self.filled + length].copy_from_slice(&remaining[..length]);
+            self.filled += length;
+            remaining = &remaining[length..];
+        }
+        Ok(data.len())
+    }
+
+    /// Writes the buffered data to the file.
+    pub fn flush(&mut self) -> Result<(), ErrorCode> {
+        if let Some(file) = &self.file {
+            if self.filled != 0 {
+                // Emptied even on failure, so that dropping after an error doesn't write it again
+                let filled = self.filled;
+                self.filled = 0;
+                file.write_chunk(&self.buffer[..filled])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the buffered data, and returns the file.
+    pub fn into_inner(mut self) -> Result<File, ErrorCode> {
+        self.flush()?;
+        Ok(self.file.take().unwrap())
+    }
+}
+
+impl fmt::Write for BufWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write(s.as_bytes()).map(|_| ()).map_err(|_| fmt::Error)
+    }
+}
+
+impl Drop for BufWriter {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+impl Output for File {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), ErrorCode> {
+        self.write_all(bytes)
+    }
 }
 
 impl Drop for File {
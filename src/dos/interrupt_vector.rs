@@ -0,0 +1,117 @@
+//! Interrupt vector table access (INT 21h AH=25h and 35h).
+//! Before changing a vector, save the original one and restore it before the program terminates.
+//! Only vectors 22h to 24h are restored automatically by DOS, from the PSP.
+
+use core::arch::asm;
+use crate::dos::far_ptr::FarPtr;
+
+/// Gets the handler address of an interrupt (INT 21h AH=35h).
+pub fn get_interrupt_vector(int_no: u8) -> FarPtr {
+    let segment: u16;
+    let offset: u16;
+    unsafe {
+        asm!("push es", "int 0x21", "mov {segment:x}, es", "pop es",
+             segment = out(reg) segment,
+             in("ax") 0x3500_u16 | int_no as u16,
+             out("bx") offset);
+    }
+    FarPtr::new(segment, offset)
+}
+
+/// Sets the handler address of an interrupt (INT 21h AH=25h).
+pub fn set_interrupt_vector(int_no: u8, handler: FarPtr) {
+    unsafe {
+        asm!("push ds", "mov ds, {segment:x}", "int 0x21", "pop ds",
+             segment = in(reg) handler.segment,
+             in("ax") 0x2500_u16 | int_no as u16,
+             in("dx") handler.offset);
+    }
+}
+
+/// Disables interrupts until dropped, for instance to update data shared with an interrupt handler.
+/// Dropping it restores the flags saved on creation, so interrupts stay disabled if they already were,
+/// and guards can be nested.
+pub struct InterruptGuard {
+    flags: u16,
+}
+
+impl InterruptGuard {
+    pub fn new() -> Self {
+        let flags: u16;
+        unsafe { asm!("pushf", "pop {flags:x}", "cli", flags = out(reg) flags) }
+        InterruptGuard { flags }
+    }
+}
+
+impl Default for InterruptGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for InterruptGuard {
+    fn drop(&mut self) {
+        unsafe { asm!("push {flags:x}", "popf", flags = in(reg) self.flags) }
+    }
+}
+
+/// Runs `f` with interrupts disabled, using an [InterruptGuard].
+/// `f` should be short: the timer and keyboard interrupts are delayed until it returns.
+///
+/// ```ignore
+/// let (ticks, overflows) = critical_section(|| unsafe { (TICKS, OVERFLOWS) });
+/// ```
+pub fn critical_section<T>(f: impl FnOnce() -> T) -> T {
+    let _guard = InterruptGuard::new();
+    f()
+}
+
+/// Wraps a `fn()` into an interrupt handler, and evaluates to its [FarPtr] for [set_interrupt_vector].
+///
+/// The generated trampoline saves all registers, points DS and ES to our segment, switches to a
+/// private 1 KB stack, calls the function and returns with IRET.
+/// The function runs with interrupts disabled unless it enables them, and the handler is not reentrant.
+/// Since it can interrupt anything, including DOS and the allocator, the function must not allocate
+/// and must not call DOS. It also must not be `#[no_mangle]`, as the trampoline refers to it by path.
+///
+/// ```ignore
+/// fn on_tick() { /* ... */ }
+/// set_interrupt_vector(0x1C, interrupt_handler!(on_tick));
+/// ```
+#[macro_export]
+macro_rules! interrupt_handler {
+    ($handler:path) => {{
+        const STACK_SIZE: usize = 1024;
+        static mut STACK: [u8; STACK_SIZE] = [0; STACK_SIZE];
+        static mut SAVED_SS: u16 = 0;
+        static mut SAVED_ESP: u32 = 0;
+
+        extern "C" fn call_handler() {
+            // type check the given path
+            let f: fn() = $handler;
+            f()
+        }
+
+        #[unsafe(naked)]
+        unsafe extern "C" fn trampoline() {
+            ::core::arch::naked_asm!(
+                "pushad", "push ds", "push es",
+                "mov ax, cs", "mov ds, ax", "mov es, ax",
+                "mov [{saved_ss}], ss", "mov [{saved_esp}], esp",
+                "mov ss, ax", "mov esp, offset {stack} + {stack_size}",
+                "cld",
+                "call {handler}",
+                "mov ss, [{saved_ss}]", "mov esp, [{saved_esp}]",
+                "pop es", "pop ds", "popad",
+                "iret",
+                handler = sym call_handler,
+                stack = sym STACK,
+                stack_size = const STACK_SIZE,
+                saved_ss = sym SAVED_SS,
+                saved_esp = sym SAVED_ESP,
+            );
+        }
+
+        $crate::dos::far_ptr::FarPtr::in_code_segment(trampoline as *const () as usize as u16)
+    }};
+}
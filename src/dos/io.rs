@@ -1,27 +1,42 @@
+//! x86 port I/O, the single place the `in` and `out` instructions are used.
+//!
+//! These functions are unsafe: writing to the wrong port, or reading a port with side effects,
+//! can wedge the machine, reset it, or confuse a device driver or the BIOS.
+
 use core::arch::asm;
 
-pub fn inb(port: usize) -> u8 {
-    let mut ret: u8;
-    unsafe {
-        asm!("in al, dx", out("al") ret, in("dx") port);
-    }
-    ret
+/// Reads a byte from a port.
+///
+/// # Safety
+/// The port must be safe to read, reading some ports acknowledges data or interrupts.
+pub unsafe fn inb(port: u16) -> u8 {
+    let value: u8;
+    unsafe { asm!("in al, dx", out("al") value, in("dx") port, options(nomem, nostack, preserves_flags)) }
+    value
 }
 
-pub fn inw(port: usize) -> u16 {
-    let mut ret: u16;
-    unsafe { asm!("in ax, dx", out("ax") ret, in("dx") port) }
-    ret
+/// Reads a word from a port.
+///
+/// # Safety
+/// The port must be safe to read, reading some ports acknowledges data or interrupts.
+pub unsafe fn inw(port: u16) -> u16 {
+    let value: u16;
+    unsafe { asm!("in ax, dx", out("ax") value, in("dx") port, options(nomem, nostack, preserves_flags)) }
+    value
 }
 
-pub fn outb(data: u8, port: usize) {
-    unsafe {
-        asm!("out dx, al", in("dx") port, in("al") data);
-    }
+/// Writes a byte to a port.
+///
+/// # Safety
+/// The write must be valid for the device behind the port.
+pub unsafe fn outb(port: u16, value: u8) {
+    unsafe { asm!("out dx, al", in("dx") port, in("al") value, options(nomem, nostack, preserves_flags)) }
 }
 
-pub fn outw(data: u16, port: usize) {
-    unsafe {
-        asm!("out dx, ax", in("dx") port, in("ax") data);
-    }
+/// Writes a word to a port.
+///
+/// # Safety
+/// The write must be valid for the device behind the port.
+pub unsafe fn outw(port: u16, value: u16) {
+    unsafe { asm!("out dx, ax", in("dx") port, in("ax") value, options(nomem, nostack, preserves_flags)) }
 }
@@ -0,0 +1,133 @@
+//! Device control (INT 21h AH=44h).
+
+use core::arch::asm;
+use crate::dos::disk::DriveLetter;
+use crate::dos::error_code::ErrorCode;
+
+/// Information word of a handle, as returned by [get_device_info].
+/// The meaning of the bits depends on whether the handle is a device or a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceInfo(u16);
+
+impl DeviceInfo {
+    const CONSOLE_INPUT: u16 = 0x0001;
+    const CONSOLE_OUTPUT: u16 = 0x0002;
+    const RAW: u16 = 0x0020;
+    const DEVICE: u16 = 0x0080;
+
+    pub const fn from_bits(bits: u16) -> Self {
+        DeviceInfo(bits)
+    }
+
+    pub const fn bits(&self) -> u16 {
+        self.0
+    }
+
+    /// True for a character device, such as CON or PRN, false for a file.
+    pub const fn is_device(&self) -> bool {
+        self.0 & Self::DEVICE != 0
+    }
+
+    /// True for the console, either for input or output.
+    pub const fn is_console(&self) -> bool {
+        self.is_device() && self.0 & (Self::CONSOLE_INPUT | Self::CONSOLE_OUTPUT) != 0
+    }
+
+    /// True if the device is in raw (binary) mode: Ctrl-C, Ctrl-Z and line editing keys aren't processed.
+    pub const fn is_raw(&self) -> bool {
+        self.is_device() && self.0 & Self::RAW != 0
+    }
+
+    /// Same information, with the raw mode bit set or cleared, for [set_device_info].
+    pub const fn with_raw(&self, raw: bool) -> Self {
+        if raw {
+            DeviceInfo(self.0 | Self::RAW)
+        } else {
+            DeviceInfo(self.0 & !Self::RAW)
+        }
+    }
+}
+
+/// Gets the information word of a handle (INT 21h AX=4400h).
+pub fn get_device_info(handle: u16) -> Result<DeviceInfo, ErrorCode> {
+    let error_code: u16;
+    let info: u16;
+    let carry: u8;
+    unsafe {
+        asm!("int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             inout("ax") 0x4400_u16 => error_code,
+             in("bx") handle,
+             out("dx") info);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(DeviceInfo::from_bits(info))
+}
+
+/// Sets the information word of a device handle (INT 21h AX=4401h). Only the low byte can be changed.
+pub fn set_device_info(handle: u16, info: u16) -> Result<(), ErrorCode> {
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             inout("ax") 0x4401_u16 => error_code,
+             in("bx") handle,
+             in("dx") info & 0x00FF);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(())
+}
+
+/// Puts a device handle in raw (binary) or cooked mode, for instance so that Ctrl-Z isn't the end of STDIN.
+pub fn set_raw_mode(handle: u16, raw: bool) -> Result<(), ErrorCode> {
+    let info = get_device_info(handle)?;
+    if !info.is_device() {
+        return Err(ErrorCode::InvalidFunctionNumber);
+    }
+    set_device_info(handle, info.with_raw(raw).bits())
+}
+
+/// Returns true if a device handle has a character ready to be read, or a file handle isn't at its end
+/// (INT 21h AX=4406h). Unlike reading, it doesn't wait.
+pub fn is_input_ready(handle: u16) -> Result<bool, ErrorCode> {
+    let error_code_or_status: u16;
+    let carry: u8;
+    unsafe {
+        asm!("int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             inout("ax") 0x4406_u16 => error_code_or_status,
+             in("bx") handle);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code_or_status as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(error_code_or_status as u8 == 0xFF)
+}
+
+/// Gets the letter a physical drive with several letters is currently accessed with, such as A: for B: on
+/// a machine with a single floppy drive (INT 21h AX=440Eh, DOS 3.2+).
+/// Returns None if the drive has a single letter. Accessing it with another letter makes DOS ask the user
+/// to swap the disks.
+pub fn get_logical_drive_map(drive: DriveLetter) -> Result<Option<DriveLetter>, ErrorCode> {
+    let error_code_or_drive: u16;
+    let carry: u8;
+    unsafe {
+        asm!("int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             inout("ax") 0x440E_u16 => error_code_or_drive,
+             // Numbered from 1 (A), 0 being the default drive
+             in("bx") drive as u16 + 1);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code_or_drive as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    match error_code_or_drive as u8 {
+        0 => Ok(None),
+        drive_number => Ok(Some(DriveLetter::from(drive_number - 1))),
+    }
+}
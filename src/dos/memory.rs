@@ -0,0 +1,263 @@
+//! Conventional memory blocks managed by DOS (INT 21h AH=48h, 49h, 4Ah and 58h), and the chain of
+//! Memory Control Blocks describing them.
+//! DOS hands out memory in 16-byte paragraphs, identified by the segment of the block.
+//! Note that a COM program owns all free conventional memory when it starts, and the heap
+//! takes most of it: only [crate::dos::allocator::DosAllocator::RESERVED_PARAGRAPHS] are left for these blocks.
+
+use core::arch::asm;
+use crate::dos::error_code::ErrorCode;
+use crate::dos::far_ptr::FarPtr;
+use crate::dos::system::dos_version_at_least;
+use crate::dos::sysvars::mcb_chain_start;
+
+/// A memory block allocated by DOS. The block is released when dropped.
+pub struct DosMemory {
+    segment: u16,
+    paragraphs: u16,
+}
+
+impl DosMemory {
+    pub const PARAGRAPH_SIZE: usize = 16;
+
+    /// Allocates a block of `paragraphs` paragraphs.
+    /// On failure, returns the error code and the size of the largest available block, in paragraphs.
+    pub fn allocate(paragraphs: u16) -> Result<DosMemory, (ErrorCode, u16)> {
+        let error_code_or_segment: u16;
+        let largest_block: u16;
+        let carry: u8;
+        unsafe {
+            asm!("mov ah, 0x48", "int 0x21", "setc {carry}",
+                 carry = out(reg_byte) carry,
+                 inout("bx") paragraphs => largest_block,
+                 out("ax") error_code_or_segment);
+        }
+        if carry != 0 {
+            return Err((ErrorCode::from_u8(error_code_or_segment as u8).unwrap_or(ErrorCode::UnknownError), largest_block));
+        }
+        Ok(DosMemory {
+            segment: error_code_or_segment,
+            paragraphs,
+        })
+    }
+
+    /// Resizes the block in place to `paragraphs` paragraphs.
+    /// On failure, returns the error code and the largest size the block can grow to, in paragraphs.
+    pub fn resize(&mut self, paragraphs: u16) -> Result<(), (ErrorCode, u16)> {
+        let error_code: u16;
+        let largest_block: u16;
+        let carry: u8;
+        unsafe {
+            asm!("push es", "mov es, {segment:x}", "mov ah, 0x4a", "int 0x21", "setc {carry}", "pop es",
+                 segment = in(reg) self.segment,
+                 carry = out(reg_byte) carry,
+                 inout("bx") paragraphs => largest_block,
+                 out("ax") error_code);
+        }
+        if carry != 0 {
+            return Err((ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError), largest_block));
+        }
+        self.paragraphs = paragraphs;
+        Ok(())
+    }
+
+    pub fn segment(&self) -> u16 {
+        self.segment
+    }
+
+    /// Size of the block, in paragraphs.
+    pub fn paragraphs(&self) -> u16 {
+        self.paragraphs
+    }
+
+    /// Size of the block, in bytes.
+    pub fn len(&self) -> usize {
+        self.paragraphs as usize * Self::PARAGRAPH_SIZE
+    }
+
+    /// True for a block of 0 paragraphs, which DOS allows.
+    pub fn is_empty(&self) -> bool {
+        self.paragraphs == 0
+    }
+
+    /// Pointer to the start of the block.
+    pub fn as_ptr(&self) -> *mut u8 {
+        FarPtr::new(self.segment, 0).as_mut_ptr()
+    }
+
+    fn release(&self) -> Result<(), ErrorCode> {
+        let error_code: u16;
+        let carry: u8;
+        unsafe {
+            asm!("push es", "mov es, {segment:x}", "mov ah, 0x49", "int 0x21", "setc {carry}", "pop es",
+                 segment = in(reg) self.segment,
+                 carry = out(reg_byte) carry,
+                 out("ax") error_code);
+        }
+        if carry != 0 {
+            return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for DosMemory {
+    fn drop(&mut self) {
+        let _ = self.release();
+    }
+}
+
+/// How DOS chooses the block for [DosMemory::allocate].
+/// The `High` strategies try the upper memory blocks first, the `HighOnly` ones only use them (DOS 5.0+).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocationStrategy {
+    FirstFit = 0x00,
+    BestFit = 0x01,
+    LastFit = 0x02,
+    FirstFitHighOnly = 0x40,
+    BestFitHighOnly = 0x41,
+    LastFitHighOnly = 0x42,
+    FirstFitHigh = 0x80,
+    BestFitHigh = 0x81,
+    LastFitHigh = 0x82,
+}
+
+impl AllocationStrategy {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0x00 => Some(Self::FirstFit),
+            0x01 => Some(Self::BestFit),
+            0x02 => Some(Self::LastFit),
+            0x40 => Some(Self::FirstFitHighOnly),
+            0x41 => Some(Self::BestFitHighOnly),
+            0x42 => Some(Self::LastFitHighOnly),
+            0x80 => Some(Self::FirstFitHigh),
+            0x81 => Some(Self::BestFitHigh),
+            0x82 => Some(Self::LastFitHigh),
+            _ => None,
+        }
+    }
+
+    fn uses_upper_memory(&self) -> bool {
+        *self as u8 & 0xC0 != 0
+    }
+}
+
+/// Gets the allocation strategy (INT 21h AX=5800h, DOS 3.0+). Always FirstFit before DOS 3.0.
+/// Returns None if DOS returns a strategy this crate doesn't know.
+pub fn get_allocation_strategy() -> Option<AllocationStrategy> {
+    if !dos_version_at_least(3, 0) {
+        return Some(AllocationStrategy::FirstFit);
+    }
+    let strategy: u16;
+    unsafe { asm!("int 0x21", inout("ax") 0x5800_u16 => strategy) }
+    AllocationStrategy::from_u8(strategy as u8)
+}
+
+/// Sets the allocation strategy (INT 21h AX=5801h, DOS 3.0+, DOS 5.0+ for the upper memory strategies).
+/// The strategy stays after the program terminates, so restore the previous one before.
+pub fn set_allocation_strategy(strategy: AllocationStrategy) -> Result<(), ErrorCode> {
+    let required_version = if strategy.uses_upper_memory() { 5 } else { 3 };
+    if !dos_version_at_least(required_version, 0) {
+        return Err(ErrorCode::InvalidFunctionNumber);
+    }
+    memory_function(0x5801, strategy as u16)
+}
+
+/// Returns true if the upper memory blocks are linked to the conventional memory chain,
+/// so that they can be allocated (INT 21h AX=5802h, DOS 5.0+). Always false before DOS 5.0.
+pub fn get_umb_link() -> bool {
+    if !dos_version_at_least(5, 0) {
+        return false;
+    }
+    let linked: u16;
+    unsafe { asm!("int 0x21", inout("ax") 0x5802_u16 => linked) }
+    linked as u8 != 0
+}
+
+/// Links or unlinks the upper memory blocks (INT 21h AX=5803h, DOS 5.0+).
+/// Fails if there are no upper memory blocks, for instance without `DOS=UMB` in CONFIG.SYS.
+/// The link state stays after the program terminates, so restore the previous one before.
+pub fn set_umb_link(linked: bool) -> Result<(), ErrorCode> {
+    if !dos_version_at_least(5, 0) {
+        return Err(ErrorCode::InvalidFunctionNumber);
+    }
+    memory_function(0x5803, linked as u16)
+}
+
+fn memory_function(function: u16, value: u16) -> Result<(), ErrorCode> {
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("int 0x21", "setc {carry}",
+             carry = out(reg_byte) carry,
+             inout("ax") function => error_code,
+             in("bx") value);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(())
+}
+
+/// A memory block, as described by its Memory Control Block, the paragraph just before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct McbInfo {
+    /// Segment of the MCB, the block itself starts at the next paragraph
+    pub segment: u16,
+    /// PSP segment of the program owning the block, 0 if free, 8 for DOS itself
+    pub owner: u16,
+    pub size_paragraphs: u16,
+    /// True for the last block of the chain, with the 'Z' signature instead of 'M'
+    pub is_last: bool,
+}
+
+impl McbInfo {
+    pub const fn data_segment(&self) -> u16 {
+        self.segment + 1
+    }
+
+    pub const fn is_free(&self) -> bool {
+        self.owner == 0
+    }
+}
+
+/// Iterator over the memory blocks, created by [memory_blocks].
+pub struct McbIter {
+    // The next block, or the error to give for it, and None once the last block or an error was reached
+    next: Option<Result<u16, ErrorCode>>,
+}
+
+impl Iterator for McbIter {
+    /// A block whose signature is neither 'M' nor 'Z', or a block that isn't the last one but ends at the top
+    /// of the memory, gives MemoryControlBlockDestroyed, and ends the iteration.
+    type Item = Result<McbInfo, ErrorCode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let segment = match self.next.take()? {
+            Ok(segment) => segment,
+            Err(error) => return Some(Err(error)),
+        };
+        let mcb = FarPtr::new(segment, 0).as_ptr::<u8>();
+        let (signature, owner, size_paragraphs) = unsafe {
+            (mcb.read_volatile(), mcb.add(1).cast::<u16>().read_unaligned(), mcb.add(3).cast::<u16>().read_unaligned())
+        };
+        let is_last = match signature {
+            b'M' => false,
+            b'Z' => true,
+            _ => return Some(Err(ErrorCode::MemoryControlBlockDestroyed)),
+        };
+        if !is_last {
+            let next_segment = segment.checked_add(size_paragraphs).and_then(|end| end.checked_add(1));
+            self.next = Some(next_segment.ok_or(ErrorCode::MemoryControlBlockDestroyed));
+        }
+        Some(Ok(McbInfo { segment, owner, size_paragraphs, is_last }))
+    }
+}
+
+/// Walks the chain of Memory Control Blocks, from the first one given by the List of Lists.
+/// Upper memory blocks are included while they are linked, see [set_umb_link].
+pub fn memory_blocks() -> McbIter {
+    McbIter {
+        next: Some(Ok(mcb_chain_start())),
+    }
+}
@@ -0,0 +1,59 @@
+//! Places formatted output can go to: the standard output ([crate::dos::console::Stdout]), a [crate::dos::file::File],
+//! or the text screen ([crate::bios::text_screen::TextScreen]), so that code writing output can be generic over them.
+//!
+//! ```ignore
+//! fn report(output: &mut impl Output, count: usize) -> Result<(), ErrorCode> {
+//!     write!(output, "{} files\r\n", count)
+//! }
+//! ```
+
+use core::fmt;
+use crate::dos::error_code::ErrorCode;
+
+pub trait Output {
+    /// Writes all the bytes.
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), ErrorCode>;
+
+    /// Writes formatted output, used by `write!` and `writeln!`. Stops at the first error, and returns it.
+    fn write_fmt(&mut self, args: fmt::Arguments) -> Result<(), ErrorCode> {
+        let mut writer = FmtWriter { output: self, error: None };
+        match fmt::write(&mut writer, args) {
+            Ok(()) => Ok(()),
+            // A formatting trait implementation failed, not the output
+            Err(_) => Err(writer.error.unwrap_or(ErrorCode::UnknownError)),
+        }
+    }
+}
+
+impl<T: Output + ?Sized> Output for &mut T {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), ErrorCode> {
+        (**self).write_bytes(bytes)
+    }
+}
+
+/// Gives the [fmt::Write] of an [Output], for the functions expecting one.
+/// The error of the output is kept, as [fmt::Error] carries none.
+pub struct FmtWriter<'a, T: Output + ?Sized> {
+    output: &'a mut T,
+    error: Option<ErrorCode>,
+}
+
+impl<'a, T: Output + ?Sized> FmtWriter<'a, T> {
+    pub fn new(output: &'a mut T) -> Self {
+        FmtWriter { output, error: None }
+    }
+
+    /// The error of the last failed write, if any.
+    pub fn error(&self) -> Option<ErrorCode> {
+        self.error
+    }
+}
+
+impl<T: Output + ?Sized> fmt::Write for FmtWriter<'_, T> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.output.write_bytes(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
@@ -1,8 +1,25 @@
 use super::exit;
+use super::console::DosWriter;
+use core::fmt::Write;
 use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Return code of a program that panicked.
+pub const PANIC_EXIT_CODE: u8 = 255;
+
+static PANICKING: AtomicBool = AtomicBool::new(false);
 
 #[panic_handler]
-fn panic(_info: &PanicInfo) -> ! {
-    println!("{}", _info);
-    exit(1);
+fn panic(info: &PanicInfo) -> ! {
+    // A panic while printing the message would recurse, just exit
+    if PANICKING.swap(true, Ordering::Relaxed) {
+        exit(PANIC_EXIT_CODE);
+    }
+    let mut writer = DosWriter {};
+    let _ = write!(writer, "PANIC: {}", info.message());
+    if let Some(location) = info.location() {
+        let _ = write!(writer, " at {}:{}", location.file(), location.line());
+    }
+    let _ = writer.write_str("\r\n");
+    exit(PANIC_EXIT_CODE);
 }
@@ -0,0 +1,152 @@
+//! Child processes (INT 21h AH=4Bh and 4Dh) and resident programs (INT 21h AH=31h).
+
+use core::arch::asm;
+use crate::dos::env::psp_segment;
+use crate::dos::error_code::ErrorCode;
+use crate::dos::far_ptr::FarPtr;
+use crate::dos::file::to_path_array;
+use crate::psp::Psp;
+
+/// EXEC parameter block for AL=00h (load and execute)
+#[repr(C, packed)]
+struct ExecParameterBlock {
+    environment_segment: u16,
+    command_tail_offset: u16,
+    command_tail_segment: u16,
+    first_fcb_offset: u16,
+    first_fcb_segment: u16,
+    second_fcb_offset: u16,
+    second_fcb_segment: u16,
+}
+
+// SS:ESP can't be saved on the stack, since DOS 2.x destroys them during EXEC
+/// Longest command tail, without its length byte and its CR.
+const MAX_COMMAND_TAIL_LEN: usize = 126;
+
+static mut SAVED_SS: u16 = 0;
+static mut SAVED_ESP: u32 = 0;
+
+/// Loads and executes a program, and returns once it has terminated (INT 21h AX=4B00h).
+///
+/// `env` is the segment of the environment block given to the child, or None to give it a copy of ours.
+/// Use [get_return_code] afterwards to get the child return code.
+/// The memory of the child is taken from the free conventional memory, so the program's own block may have to be shrunk first.
+/// Fails with InvalidData if the command line doesn't fit in the 126 bytes of a command tail, with the space added before it.
+pub fn exec(program: &str, cmdline: &str, env: Option<u16>) -> Result<(), ErrorCode> {
+    let program_array = to_path_array(program)?;
+
+    // Length-prefixed, CR-terminated command tail, starting with a space as DOS does
+    let mut command_tail: [u8; 128] = [0; 128]; // To be sure of the segment
+    let mut command_tail_len = 0;
+    if !cmdline.is_empty() && !cmdline.starts_with(' ') {
+        command_tail[1] = b' ';
+        command_tail_len = 1;
+    }
+    if command_tail_len + cmdline.len() > MAX_COMMAND_TAIL_LEN {
+        return Err(ErrorCode::InvalidData);
+    }
+    for &c in cmdline.as_bytes() {
+        command_tail[1 + command_tail_len] = c;
+        command_tail_len += 1;
+    }
+    command_tail[0] = command_tail_len as u8;
+    command_tail[1 + command_tail_len] = b'\r';
+
+    let data_segment: u16;
+    unsafe { asm!("mov {0:x}, ds", out(reg) data_segment) }
+    let parameter_block = ExecParameterBlock {
+        environment_segment: env.unwrap_or(0),
+        command_tail_offset: command_tail.as_ptr() as u16,
+        command_tail_segment: data_segment,
+        // The default FCBs of our PSP
+        first_fcb_offset: Psp::FIRST_FCB_OFFSET,
+        first_fcb_segment: data_segment,
+        second_fcb_offset: Psp::SECOND_FCB_OFFSET,
+        second_fcb_segment: data_segment,
+    };
+
+    let error_code: u16;
+    let carry: u8;
+    unsafe {
+        asm!("push bp", "push si", "push ds", "push es",
+             "mov cs:[{saved_ss}], ss", "mov cs:[{saved_esp}], esp",
+             "push ds", "pop es",
+             "mov ax, 0x4B00", "int 0x21",
+             "cli", "mov ss, cs:[{saved_ss}]", "mov esp, cs:[{saved_esp}]", "sti",
+             "pop es", "pop ds", "pop si", "pop bp",
+             "setc cl",
+             saved_ss = sym SAVED_SS,
+             saved_esp = sym SAVED_ESP,
+             in("dx") program_array.as_c_str() as u16,
+             in("bx") &parameter_block as *const ExecParameterBlock as u16,
+             out("ax") error_code,
+             out("cl") carry,
+             lateout("bx") _, lateout("dx") _, out("di") _);
+    }
+    if carry != 0 {
+        return Err(ErrorCode::from_u8(error_code as u8).unwrap_or(ErrorCode::UnknownError));
+    }
+    Ok(())
+}
+
+/// How a child process terminated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminationReason {
+    Normal = 0,
+    CtrlC = 1,
+    CriticalError = 2,
+    StayResident = 3,
+    Unknown,
+}
+
+impl From<u8> for TerminationReason {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Normal,
+            1 => Self::CtrlC,
+            2 => Self::CriticalError,
+            3 => Self::StayResident,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Return code and termination reason of a child process.
+#[derive(Debug, Clone, Copy)]
+pub struct ChildExit {
+    pub code: u8,
+    pub reason: TerminationReason,
+}
+
+/// Gets the return code of the last child process (INT 21h AH=4Dh).
+///
+/// DOS clears the value once read, so only the first call after the child terminates is meaningful.
+pub fn get_return_code() -> ChildExit {
+    let ret: u16;
+    unsafe { asm!("mov ah, 0x4D", "int 0x21", out("ax") ret) }
+    ChildExit {
+        code: ret as u8,
+        reason: TerminationReason::from((ret >> 8) as u8),
+    }
+}
+
+/// Terminates the program but keeps `resident_paragraphs` of its memory, from the PSP, allocated (INT 21h AH=31h).
+/// Interrupt handlers installed with [crate::dos::interrupt_vector::set_interrupt_vector] keep running afterwards,
+/// provided they are in the resident part. Other memory blocks of the program, such as the heap, stay allocated too.
+/// Use [resident_paragraphs] to compute the size to keep.
+pub fn stay_resident(return_code: u8, resident_paragraphs: u16) -> ! {
+    unsafe {
+        asm!("mov ah, 0x31", "int 0x21",
+             in("al") return_code,
+             in("dx") resident_paragraphs,
+             options(noreturn));
+    }
+}
+
+/// Number of paragraphs from the start of the PSP to `end`, for [stay_resident].
+/// Everything before `end` is kept, which must include the interrupt handlers and the data they use.
+pub fn resident_paragraphs(end: *const u8) -> u16 {
+    let psp_address = FarPtr::new(psp_segment(), 0).as_ptr::<u8>() as u32;
+    let resident_bytes = (end as u32).wrapping_sub(psp_address);
+    resident_bytes.div_ceil(16) as u16
+}
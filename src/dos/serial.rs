@@ -0,0 +1,60 @@
+//! Auxiliary device (AUX, usually COM1) through DOS (INT 21h AH=03h/04h).
+//!
+//! DOS gives no way to read the line status, detect errors or set the baud rate of the port:
+//! [crate::bios::serial] (INT 14h) does. Reading waits for a character, forever if the port is stuck,
+//! unless [Serial::read_with_timeout] is used.
+
+use core::arch::asm;
+use crate::bios::timer::{read_tick_count, TICKS_PER_DAY};
+use crate::dos::error_code::ErrorCode;
+use crate::dos::file::STDAUX;
+use crate::dos::ioctl::is_input_ready;
+use crate::dos::output::Output;
+
+/// The auxiliary device.
+#[derive(Debug, Default)]
+pub struct Serial;
+
+impl Serial {
+    pub fn new() -> Self {
+        Serial
+    }
+
+    /// Reads a byte, waiting until one is received (INT 21h AH=03h).
+    pub fn read_byte(&self) -> u8 {
+        let byte: u8;
+        unsafe { asm!("int 0x21", in("ah") 0x03_u8, out("al") byte) }
+        byte
+    }
+
+    /// Writes a byte, waiting until it is sent (INT 21h AH=04h).
+    pub fn write_byte(&self, byte: u8) {
+        unsafe { asm!("int 0x21", in("ah") 0x04_u8, in("dl") byte, lateout("al") _) }
+    }
+
+    /// Reads a byte, or returns None if none is received within `ticks` timer ticks (about 55 ms each).
+    /// The input status of the device is polled, so that reading never waits.
+    pub fn read_with_timeout(&self, ticks: u32) -> Option<u8> {
+        let start = read_tick_count();
+        loop {
+            if is_input_ready(STDAUX).unwrap_or(false) {
+                return Some(self.read_byte());
+            }
+            let now = read_tick_count();
+            let elapsed = if now >= start { now - start } else { now + TICKS_PER_DAY - start };
+            if elapsed >= ticks {
+                return None;
+            }
+        }
+    }
+}
+
+impl Output for Serial {
+    /// Never fails, as DOS reports no error for the device.
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), ErrorCode> {
+        for &byte in bytes {
+            self.write_byte(byte);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+//! DOS system-wide settings and flags.
+
+use core::arch::asm;
+use crate::dos::disk::DriveLetter;
+use crate::dos::far_ptr::FarPtr;
+use crate::dos::interrupt_vector::{get_interrupt_vector, set_interrupt_vector};
+use core::ptr::{addr_of, addr_of_mut};
+
+/// DOS version, comparable to check for features availability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DosVersion {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl DosVersion {
+    pub const fn new(major: u8, minor: u8) -> Self {
+        DosVersion { major, minor }
+    }
+}
+
+/// Gets the DOS version (INT 21h AH=30h).
+pub fn dos_version() -> DosVersion {
+    let version: u16;
+    unsafe { asm!("mov ah, 0x30", "int 0x21", out("ax") version, out("bx") _, out("cx") _) }
+    DosVersion::new(version as u8, (version >> 8) as u8)
+}
+
+/// Returns true if the DOS version is at least `major.minor`.
+pub fn dos_version_at_least(major: u8, minor: u8) -> bool {
+    dos_version() >= DosVersion::new(major, minor)
+}
+
+/// Gets whether extended Ctrl-Break checking is enabled (INT 21h AX=3300h).
+/// When disabled, DOS only checks for Ctrl-Break during console and printer I/O.
+pub fn get_ctrl_break() -> bool {
+    let state: u8;
+    unsafe { asm!("int 0x21", in("ax") 0x3300_u16, out("dl") state) }
+    state != 0
+}
+
+/// Enables or disables extended Ctrl-Break checking (INT 21h AX=3301h).
+/// When enabled, DOS checks for Ctrl-Break during every DOS call, which programs doing heavy stdin processing often want.
+pub fn set_ctrl_break(enabled: bool) {
+    unsafe { asm!("int 0x21", in("ax") 0x3301_u16, in("dl") enabled as u8) }
+}
+
+/// Gets whether DOS reads back the sectors it writes to check them (INT 21h AH=54h).
+pub fn get_verify() -> bool {
+    let state: u8;
+    unsafe { asm!("int 0x21", in("ah") 0x54_u8, out("al") state) }
+    state != 0
+}
+
+/// Enables or disables the read back of written sectors (INT 21h AH=2Eh), slowing writes down but catching bad sectors.
+pub fn set_verify(enabled: bool) {
+    // DL must be 0 before DOS 3.0
+    unsafe { asm!("int 0x21", in("ah") 0x2E_u8, in("al") enabled as u8, in("dl") 0_u8) }
+}
+
+/// Gets the switch character, the `/` before command line options (INT 21h AX=3700h).
+/// Undocumented, but supported by every DOS version up to 4.0; DOS 5.0+ always returns `/`.
+pub fn get_switch_char() -> u8 {
+    let switch_char: u8;
+    unsafe { asm!("int 0x21", inout("ax") 0x3700_u16 => _, out("dl") switch_char) }
+    switch_char
+}
+
+/// Sets the switch character, such as `-` (INT 21h AX=3701h). Undocumented, and ignored by DOS 5.0+.
+pub fn set_switch_char(switch_char: u8) {
+    unsafe { asm!("int 0x21", inout("ax") 0x3701_u16 => _, in("dl") switch_char) }
+}
+
+/// Sets the "wait for external event" flag (INT 21h AH=64h, DOS 3.2+), also known as the device driver
+/// lookahead flag. When clear, DOS calls the INT 2Ah idle hook while it waits for console input;
+/// when set, it skips it. Undocumented, and only meaningful to TSRs and multitaskers hooking that idle loop.
+pub fn set_wait_for_external_event(wait: bool) {
+    unsafe { asm!("int 0x21", in("ah") 0x64_u8, in("al") wait as u8) }
+}
+
+static mut CTRL_C_HANDLER: Option<fn()> = None;
+static mut OLD_CTRL_C_VECTOR: FarPtr = FarPtr::new(0, 0);
+
+fn call_ctrl_c_handler() {
+    if let Some(handler) = unsafe { addr_of!(CTRL_C_HANDLER).read() } {
+        handler();
+    }
+}
+
+/// Calls `handler` instead of terminating the program when Ctrl-C or Ctrl-Break is pressed (INT 23h).
+/// DOS checks for it during console I/O, or during every call if [set_ctrl_break] is enabled.
+///
+/// The handler runs inside the DOS call that detected the key, through [crate::interrupt_handler!], so it must not
+/// call DOS or allocate: console I/O would check for Ctrl-C again and reenter the handler, whose stack isn't reentrant.
+/// Setting a flag, checked by the program afterwards, is enough. When it returns, DOS goes on with the interrupted call. Installing again replaces the handler, keeping the original vector.
+/// DOS restores the original vector when the program terminates.
+pub fn install_ctrl_c_handler(handler: fn()) {
+    unsafe {
+        if addr_of!(CTRL_C_HANDLER).read().is_none() {
+            addr_of_mut!(OLD_CTRL_C_VECTOR).write(get_interrupt_vector(0x23));
+        }
+        addr_of_mut!(CTRL_C_HANDLER).write(Some(handler));
+    }
+    // The trampoline returns with IRET, telling DOS to continue
+    set_interrupt_vector(0x23, crate::interrupt_handler!(call_ctrl_c_handler));
+}
+
+/// Restores the Ctrl-C handler replaced by [install_ctrl_c_handler].
+pub fn uninstall_ctrl_c_handler() {
+    unsafe {
+        if addr_of!(CTRL_C_HANDLER).read().is_none() {
+            return;
+        }
+        set_interrupt_vector(0x23, addr_of!(OLD_CTRL_C_VECTOR).read());
+        addr_of_mut!(CTRL_C_HANDLER).write(None);
+    }
+}
+
+/// Gets the drive the system was booted from (INT 21h AX=3305h), or None before DOS 4.0, which lacks the function.
+pub fn get_boot_drive() -> Option<DriveLetter> {
+    if !dos_version_at_least(4, 0) {
+        return None;
+    }
+    let drive_number: u8;
+    unsafe { asm!("int 0x21", inout("ax") 0x3305_u16 => _, out("dl") drive_number) }
+    // AL=05h numbers drives from 1 (A)
+    Some(DriveLetter::from(drive_number.wrapping_sub(1)))
+}
+
+/// Gets a pointer to the InDOS flag, non-zero while DOS is executing an INT 21h call (INT 21h AH=34h).
+/// The flag doesn't move, so the pointer can be fetched once, before going resident.
+/// A TSR must also check the critical error flag, the byte just before InDOS on most DOS versions:
+/// DOS functions above 0Ch can't be called while it is set, even if InDOS is 1 because DOS is waiting for input.
+pub fn in_dos_flag() -> *const u8 {
+    let segment: u16;
+    let offset: u16;
+    unsafe {
+        asm!("push es", "mov ah, 0x34", "int 0x21", "mov {segment:x}, es", "pop es",
+             segment = out(reg) segment,
+             out("ax") _,
+             out("bx") offset);
+    }
+    FarPtr::new(segment, offset).as_ptr()
+}
+
+/// Returns true if DOS is executing an INT 21h call, so that it can't be called from an interrupt handler.
+pub fn is_dos_busy() -> bool {
+    unsafe { in_dos_flag().read_volatile() != 0 }
+}
@@ -0,0 +1,64 @@
+//! The DOS internal variables, or "List of Lists" (INT 21h AH=52h).
+//!
+//! Undocumented, but stable since DOS 2.0: MEM, and most memory and drive tools, rely on it.
+//! Only the fields at the same offset on every version from 3.1 are exposed.
+
+use core::arch::asm;
+use crate::dos::far_ptr::FarPtr;
+use crate::dos::system::dos_version_at_least;
+
+/// Gets a pointer to the List of Lists (INT 21h AH=52h).
+pub fn get_list_of_lists() -> *const u8 {
+    let segment: u16;
+    let offset: u16;
+    unsafe {
+        asm!("push es", "mov ah, 0x52", "int 0x21", "mov {segment:x}, es", "pop es",
+             segment = out(reg) segment,
+             out("ax") _,
+             out("bx") offset);
+    }
+    FarPtr::new(segment, offset).as_ptr()
+}
+
+fn read_u8(offset: isize) -> u8 {
+    unsafe { get_list_of_lists().offset(offset).read() }
+}
+
+fn read_u16(offset: isize) -> u16 {
+    unsafe { get_list_of_lists().offset(offset).cast::<u16>().read_unaligned() }
+}
+
+fn read_far_ptr(offset: isize) -> FarPtr {
+    FarPtr::new(read_u16(offset + 2), read_u16(offset))
+}
+
+/// Segment of the first Memory Control Block, the word before the List of Lists.
+pub fn mcb_chain_start() -> u16 {
+    read_u16(-2)
+}
+
+/// Far pointer to the first Drive Parameter Block, the others being linked from it.
+pub fn first_dpb() -> FarPtr {
+    read_far_ptr(0x00)
+}
+
+/// Far pointer to the first System File Table, holding the open files.
+pub fn first_sft() -> FarPtr {
+    read_far_ptr(0x04)
+}
+
+/// Far pointer to the array of Current Directory Structures, one per drive up to LASTDRIVE (DOS 3.1+).
+pub fn current_directory_structures() -> Option<FarPtr> {
+    if !dos_version_at_least(3, 10) {
+        return None;
+    }
+    Some(read_far_ptr(0x16))
+}
+
+/// Number of drives, set by `LASTDRIVE=` in CONFIG.SYS (DOS 3.1+).
+pub fn last_drive() -> Option<u8> {
+    if !dos_version_at_least(3, 10) {
+        return None;
+    }
+    Some(read_u8(0x21))
+}
@@ -39,4 +39,59 @@ pub(crate) fn allocator_test() {
     for i in 0..10 {
         assert_eq!(vec1[i], 12);
     }
-}
\ No newline at end of file
+
+    // Shrink then grow again, the values must survive reallocations
+    vec1.shrink_to_fit();
+    assert!(vec1.capacity() >= 10);
+    for i in 0..1000 {
+        vec1.push(i);
+    }
+    for &value in vec1.iter().take(10) {
+        assert_eq!(value, 12);
+    }
+    for i in 0..1000 {
+        assert_eq!(vec1[i + 10], i);
+    }
+
+    // Interleaved allocations, freeing every other one to fragment the heap
+    let mut boxes: Vec<Box<[u32; 16]>> = Vec::new();
+    for i in 0..64 {
+        boxes.push(Box::new([i; 16]));
+    }
+    let mut i = 0;
+    boxes.retain(|_| {
+        i += 1;
+        i % 2 == 0
+    });
+    let mut strings: Vec<String> = Vec::new();
+    for i in 0..32 {
+        let mut string = String::new();
+        for _ in 0..i {
+            string.push('x');
+        }
+        strings.push(string);
+    }
+    for (i, boxed) in boxes.iter().enumerate() {
+        assert!(boxed.iter().all(|&value| value == (i * 2 + 1) as u32));
+    }
+    for (i, string) in strings.iter().enumerate() {
+        assert_eq!(string.len(), i);
+        assert!(string.bytes().all(|byte| byte == b'x'));
+    }
+
+    // Alignment requests larger than the block alignment
+    #[repr(align(64))]
+    struct Aligned(u8);
+    let small = Box::new(1_u8);
+    let aligned = Box::new(Aligned(42));
+    assert_eq!(&*aligned as *const Aligned as usize % 64, 0);
+    assert_eq!(aligned.0, 42);
+    assert_eq!(*small, 1);
+    drop(boxes);
+    drop(strings);
+    drop(aligned);
+
+    // Everything was freed, so a large allocation should fit again
+    let large = vec![0xA5_u8; 100_000];
+    assert!(large.iter().all(|&byte| byte == 0xA5));
+}
@@ -0,0 +1,15 @@
+use rust_dos::*;
+use bios::disk::{get_drive_params, read_sectors, Chs, SECTOR_SIZE};
+
+/// Needs a hard disk visible to the BIOS, such as a DOSBox `imgmount`ed image.
+#[allow(dead_code)]
+pub(crate) fn boot_sector_test() {
+    let geometry = get_drive_params(0x80).unwrap();
+    assert!(geometry.sectors_per_track > 0);
+    let mut buffer = [0; SECTOR_SIZE];
+    assert_eq!(read_sectors(0x80, Chs::new(0, 0, 1), 1, &mut buffer).unwrap(), 1);
+    assert_eq!(&buffer[510..], &[0x55, 0xAA]);
+    assert_eq!(Chs::from_cx(Chs::new(0x3FF, 0, 63).to_cx(), 0), Chs::new(0x3FF, 0, 63));
+    println!("Boot sector read, {} cylinders, {} heads, {} sectors per track",
+             geometry.cylinders, geometry.heads, geometry.sectors_per_track);
+}
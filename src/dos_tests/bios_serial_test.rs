@@ -0,0 +1,13 @@
+use rust_dos::*;
+use bios::serial::{init, status, LineStatus, SerialParams};
+
+/// COM1 must exist.
+#[allow(dead_code)]
+pub(crate) fn bios_serial_test() {
+    init(0, SerialParams::default());
+    let (line_status, modem_status) = status(0);
+    assert!(!line_status.contains(LineStatus::TIMEOUT));
+    // Nothing sent yet
+    assert!(line_status.contains(LineStatus::TRANSMIT_HOLDING_EMPTY));
+    println!("COM1 line status: {:#04x}, modem status: {:#04x}", line_status.bits(), modem_status.bits());
+}
@@ -0,0 +1,83 @@
+use rust_dos::*;
+use crate::interrupts::{direct_console_read, direct_console_write, has_input_waiting};
+
+#[allow(dead_code)]
+pub(crate) fn direct_console_write_test() {
+    for &ch in b"Direct console output\r\n" {
+        direct_console_write(ch);
+    }
+    println!("Characters written");
+}
+
+/// Needs a key press.
+#[allow(dead_code)]
+pub(crate) fn direct_console_read_test() {
+    while direct_console_read().is_some() {}
+    assert_eq!(direct_console_read(), None);
+    println!("Press a key");
+    let ch = loop {
+        if let Some(ch) = direct_console_read() {
+            break ch;
+        }
+    };
+    println!("Read {:#04x}", ch);
+}
+
+/// Type a few keys before running, then a line when prompted.
+#[allow(dead_code)]
+pub(crate) fn has_input_waiting_test() {
+    use dos::console::stdin;
+
+    while has_input_waiting() {
+        if direct_console_read().is_none() {
+            break;
+        }
+    }
+    assert!(!has_input_waiting());
+    println!("No input waiting once consumed");
+
+    print!("Type a line: ");
+    let line_length = stdin().read_line().len();
+    // The line and its CR were consumed by read_line
+    assert!(!has_input_waiting());
+    println!("No input waiting after reading {} characters", line_length);
+}
+
+/// Echoes lines until an empty one is entered.
+#[allow(dead_code)]
+pub(crate) fn stdin_test() {
+    use dos::console::stdin;
+
+    let mut stdin = stdin();
+    println!("Type lines, and an empty line to stop");
+    let mut line_count = 0;
+    loop {
+        print!("> ");
+        let line = stdin.read_line();
+        if line.is_empty() {
+            break;
+        }
+        println!("{} ({} characters)", line, line.len());
+        line_count += 1;
+    }
+    print!("Press a key: ");
+    let ch = stdin.read_char();
+    println!("{:#04x} read without echo", ch);
+    print!("Press a key: ");
+    let ch = stdin.read_char_echo();
+    println!(" {:#04x} read with echo, after {} lines", ch, line_count);
+}
+
+#[allow(dead_code)]
+pub(crate) fn print_at_test() {
+    use bios::video::{get_active_page, get_cursor_position};
+
+    let page = get_active_page();
+    let position = get_cursor_position(page);
+    print_at!(10, 20, "Score: {:05}", 42);
+    assert_eq!(get_cursor_position(page), position);
+    // Clamped to the last row
+    print_at!(30, 0, "Bottom row");
+    assert_eq!(get_cursor_position(page), position);
+    println!("Printed at (10, 20), cursor restored");
+}
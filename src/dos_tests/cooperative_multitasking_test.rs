@@ -1,14 +1,14 @@
 use dos::*;
 use rust_dos::*;
+use core::sync::atomic::{AtomicU32, Ordering};
+use dos::cooperative_multitasking::{run, spawn, yield_now};
 
 #[allow(dead_code)]
 pub(crate) fn cooperative_multitasking_test() {
     add_cooperative_task!(task_2_main).unwrap();
     println!("Hello from main task!");
-    yield_cooperative_task!();
+    run();
     println!("Hello from main task! (bis)");
-    yield_cooperative_task!();
-    println!("Hello from main task! (tris)");
 }
 
 fn task_2_main() {
@@ -29,3 +29,35 @@ fn task_3_main() {
         yield_cooperative_task!();
     }
 }
+
+static COUNTER_A: AtomicU32 = AtomicU32::new(0);
+static COUNTER_B: AtomicU32 = AtomicU32::new(0);
+
+#[allow(dead_code)]
+pub(crate) fn task_test() {
+    spawn(task_a).unwrap();
+    spawn(task_b).unwrap();
+    run();
+    assert_eq!(COUNTER_A.load(Ordering::Relaxed), 3);
+    assert_eq!(COUNTER_B.load(Ordering::Relaxed), 3);
+    println!("Both tasks finished");
+}
+
+fn task_a() {
+    for i in 0..3 {
+        // task_b ran once between each of our steps
+        assert_eq!(COUNTER_B.load(Ordering::Relaxed), i);
+        COUNTER_A.fetch_add(1, Ordering::Relaxed);
+        println!("Task A: {}", i);
+        yield_now();
+    }
+}
+
+fn task_b() {
+    for i in 0..3 {
+        assert_eq!(COUNTER_A.load(Ordering::Relaxed), i + 1);
+        COUNTER_B.fetch_add(1, Ordering::Relaxed);
+        println!("Task B: {}", i);
+        yield_now();
+    }
+}
@@ -0,0 +1,41 @@
+use rust_dos::*;
+use dos::country::{dbcs_lead_byte_table, get_code_page, get_collate_table, get_country_info, get_uppercase_table,
+                   is_dbcs_lead_byte};
+
+#[allow(dead_code)]
+pub(crate) fn country_info_test() {
+    let country_info = get_country_info().unwrap();
+    assert!(country_info.thousands_separator.is_ascii());
+    assert!(country_info.decimal_separator.is_ascii());
+    assert_ne!(country_info.thousands_separator, country_info.decimal_separator);
+    println!("Country {}: {:?}, currency {}, 1{}000{}50", country_info.country_code, country_info.date_format,
+             country_info.currency, country_info.thousands_separator, country_info.decimal_separator);
+}
+
+#[allow(dead_code)]
+pub(crate) fn code_page_test() {
+    let code_page = get_code_page().unwrap();
+    assert_ne!(code_page.active, 0);
+    println!("Code page {} (system {})", code_page.active, code_page.system);
+}
+
+#[allow(dead_code)]
+pub(crate) fn country_tables_test() {
+    let uppercase_table = get_uppercase_table();
+    assert_eq!(uppercase_table.len(), 256);
+    assert_eq!(uppercase_table[b'a' as usize], b'A');
+    assert_eq!(uppercase_table[b'A' as usize], b'A');
+    assert_eq!(uppercase_table[b'1' as usize], b'1');
+    let collate_table = get_collate_table();
+    assert_eq!(collate_table.len(), 256);
+    assert!(collate_table[b'a' as usize] < collate_table[b'b' as usize]);
+    println!("Uppercase and collate tables read");
+}
+
+/// Run on a code page without double-byte characters, such as 437.
+#[allow(dead_code)]
+pub(crate) fn dbcs_test() {
+    assert!(dbcs_lead_byte_table().is_empty());
+    assert!(!is_dbcs_lead_byte(0x81));
+    println!("No DBCS lead bytes");
+}
@@ -0,0 +1,30 @@
+use rust_dos::*;
+use core::ptr::{addr_of, addr_of_mut};
+use dos::critical_error::{install_critical_error_handler, uninstall_critical_error_handler, CriticalError, CriticalResponse};
+use dos::disk::DriveLetter;
+use dos::error_code::ErrorCode;
+use dos::file::File;
+
+static mut LAST_ERROR: Option<CriticalError> = None;
+
+fn on_critical_error(error: CriticalError) -> CriticalResponse {
+    unsafe { addr_of_mut!(LAST_ERROR).write(Some(error)) };
+    CriticalResponse::Fail
+}
+
+/// Manual: A: must exist with no disk in it, such as an empty floppy drive.
+/// DOSBox doesn't raise critical errors, use a real machine or an emulator like 86Box.
+#[allow(dead_code)]
+pub(crate) fn critical_error_handler_test() {
+    install_critical_error_handler(on_critical_error);
+    let result = File::open("A:\\NOTREADY.TXT");
+    uninstall_critical_error_handler();
+
+    assert!(matches!(result, Err(ErrorCode::FailOnInterrupt24h)));
+    let error = unsafe { addr_of!(LAST_ERROR).read() }.unwrap();
+    assert_eq!(error.code, ErrorCode::DriveNotReady);
+    assert_eq!(error.drive, Some(DriveLetter::A));
+    assert!(!error.is_write);
+    assert!(error.can_fail);
+    println!("Critical error {:?} on drive A: failed the call", error.code);
+}
@@ -0,0 +1,49 @@
+use rust_dos::*;
+use dos::datetime::{Date, Time};
+
+#[allow(dead_code)]
+pub(crate) fn dos_word_test() {
+    // The epoch
+    assert_eq!(Date::from_dos_word(0x0021), Date::new(1980, 1, 1));
+    assert_eq!(Date::new(1980, 1, 1).to_dos_word(), 0x0021);
+    assert_eq!(Time::from_dos_word(0x0000), Time::new(0, 0, 0));
+    assert_eq!(Time::new(0, 0, 0).to_dos_word(), 0x0000);
+
+    assert_eq!(Date::new(1994, 7, 21).to_dos_word(), 0x1CF5);
+    assert_eq!(Date::from_dos_word(0x1CF5), Date::new(1994, 7, 21));
+    assert_eq!(Date::new(2107, 12, 31).to_dos_word(), 0xFF9F);
+    assert_eq!(Time::new(13, 37, 42).to_dos_word(), 0x6CB5);
+    assert_eq!(Time::from_dos_word(0x6CB5), Time::new(13, 37, 42));
+    assert_eq!(Time::new(23, 59, 58).to_dos_word(), 0xBF7D);
+
+    // 2-second granularity
+    assert_eq!(Time::from_dos_word(Time::new(12, 0, 59).to_dos_word()), Time::new(12, 0, 58));
+    // Clamped years
+    assert_eq!(Date::from_dos_word(Date::new(1970, 1, 1).to_dos_word()).year, 1980);
+    assert_eq!(Date::from_dos_word(Date::new(2200, 1, 1).to_dos_word()).year, 2107);
+    for word in [0x0021, 0x1CF5, 0x5A8E, 0xFF9F] {
+        assert_eq!(Date::from_dos_word(word).to_dos_word(), word);
+    }
+    println!("DOS date and time words OK");
+}
+
+#[allow(dead_code)]
+pub(crate) fn clock_test() {
+    use dos::datetime::{Clock, DateTime};
+
+    let now = Clock::now();
+    assert!(now.date.year >= 1980 && now.date.year <= 2107);
+    assert!((1..=12).contains(&now.date.month) && (1..=31).contains(&now.date.day));
+    assert!(now.time.hour < 24 && now.time.minute < 60 && now.time.second < 60);
+    assert!(now.total_seconds_since_midnight() < 86400);
+    assert!(Clock::now() >= now);
+
+    let timestamp = DateTime::new(Date::new(1994, 7, 21), Time::new(13, 37, 42));
+    assert_eq!(timestamp.to_dos_datetime_words(), (0x1CF5, 0x6CB5));
+    assert_eq!(DateTime::from_dos_datetime_words(0x1CF5, 0x6CB5), timestamp);
+    assert_eq!(timestamp.total_seconds_since_midnight(), 13 * 3600 + 37 * 60 + 42);
+    let (date, time) = now.to_dos_datetime_words();
+    assert_eq!(DateTime::from_dos_datetime_words(date, time).date, now.date);
+    println!("{}-{:02}-{:02} {:02}:{:02}:{:02}", now.date.year, now.date.month, now.date.day,
+             now.time.hour, now.time.minute, now.time.second);
+}
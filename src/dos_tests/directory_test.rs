@@ -0,0 +1,40 @@
+use rust_dos::*;
+use dos::directory::{canonicalize, change_directory, current_directory, make_directory, remove_directory};
+use dos::disk::DriveLetter;
+use dos::error_code::ErrorCode;
+
+#[allow(dead_code)]
+pub(crate) fn directory_test() {
+    make_directory("RDTEST").unwrap();
+    assert!(matches!(make_directory("RDTEST"), Err(ErrorCode::AccessDenied)));
+    change_directory("RDTEST").unwrap();
+    change_directory("..").unwrap();
+    remove_directory("RDTEST").unwrap();
+    assert!(matches!(change_directory("RDTEST"), Err(ErrorCode::PathNotFound)));
+    println!("Directory created, entered and removed");
+}
+
+#[allow(dead_code)]
+pub(crate) fn current_directory_test() {
+    change_directory("C:\\").unwrap();
+    assert_eq!(current_directory(DriveLetter::C).unwrap(), "C:\\");
+    make_directory("C:\\RDTEST").unwrap();
+    change_directory("C:\\RDTEST").unwrap();
+    assert_eq!(current_directory(DriveLetter::C).unwrap(), "C:\\RDTEST");
+    change_directory("C:\\").unwrap();
+    remove_directory("C:\\RDTEST").unwrap();
+    println!("Current directory round-trip OK");
+}
+
+/// C: must be the current drive.
+#[allow(dead_code)]
+pub(crate) fn canonicalize_test() {
+    make_directory("C:\\RDTEST").unwrap();
+    change_directory("C:\\RDTEST").unwrap();
+    assert_eq!(canonicalize("..\\foo").unwrap(), "C:\\FOO");
+    assert_eq!(canonicalize("bar.txt").unwrap(), "C:\\RDTEST\\BAR.TXT");
+    assert_eq!(canonicalize(".\\SUB\\..\\BAZ").unwrap(), "C:\\RDTEST\\BAZ");
+    change_directory("C:\\").unwrap();
+    remove_directory("C:\\RDTEST").unwrap();
+    println!("Paths canonicalized");
+}
@@ -0,0 +1,116 @@
+use rust_dos::*;
+use alloc::format;
+use core::convert::TryFrom;
+use dos::disk::{drives, get_allocation_info_for_specified_drive, get_default_drive, get_disk_parameter_block,
+                get_disk_parameter_block_for_default_drive, get_free_disk_space, logical_drive_count,
+                set_default_drive, DriveLetter};
+
+#[allow(dead_code)]
+pub(crate) fn disk_space_test() {
+    let disk_space = get_free_disk_space(DriveLetter::C).unwrap();
+    assert!(disk_space.free_bytes <= disk_space.total_bytes);
+    assert_ne!(disk_space.bytes_per_cluster, 0);
+    println!("C: {} bytes free of {}", disk_space.free_bytes, disk_space.total_bytes);
+}
+
+#[allow(dead_code)]
+pub(crate) fn drive_letter_test() {
+    assert_eq!(DriveLetter::try_from_u8(0), Ok(DriveLetter::A));
+    assert_eq!(DriveLetter::try_from_u8(25), Ok(DriveLetter::Z));
+    assert!(DriveLetter::try_from_u8(26).is_err());
+    assert_eq!(DriveLetter::from(26), DriveLetter::Unknown);
+    assert_eq!(DriveLetter::try_from('c'), Ok(DriveLetter::C));
+    assert_eq!(DriveLetter::try_from('Z'), Ok(DriveLetter::Z));
+    assert!(DriveLetter::try_from('@').is_err());
+    assert!(DriveLetter::try_from('[').is_err());
+    assert!(DriveLetter::try_from('1').is_err());
+    assert_eq!(DriveLetter::C.as_char(), Some('C'));
+    assert_eq!(DriveLetter::Unknown.as_char(), None);
+    assert_eq!(format!("{}:\\", DriveLetter::D), "D:\\");
+    assert_eq!(format!("{}", DriveLetter::Unknown), "?");
+    println!("Drive letter conversions OK");
+}
+
+#[allow(dead_code)]
+pub(crate) fn absolute_sector_test() {
+    use bios::disk::SECTOR_SIZE;
+    use dos::disk::read_absolute_sectors;
+
+    let mut boot_sector = [0; SECTOR_SIZE];
+    read_absolute_sectors(DriveLetter::C, 0, 1, &mut boot_sector).unwrap();
+    assert_eq!(&boot_sector[510..], &[0x55, 0xAA]);
+    // File system type of the extended BIOS parameter block
+    assert_eq!(&boot_sector[0x36..0x39], b"FAT");
+    println!("C: boot sector read, {} bytes per sector", u16::from_le_bytes([boot_sector[11], boot_sector[12]]));
+}
+
+#[allow(dead_code)]
+pub(crate) fn disk_parameter_block_test() {
+    let default_drive = get_default_drive();
+    let dpb = get_disk_parameter_block_for_default_drive().unwrap();
+    assert_eq!(get_disk_parameter_block(default_drive).unwrap(), dpb);
+    assert_eq!(dpb.drive, default_drive);
+    let allocation_info = get_allocation_info_for_specified_drive(default_drive).unwrap();
+    assert_eq!(dpb.bytes_per_sector, allocation_info.sector_size);
+    assert_eq!(dpb.cluster_count, allocation_info.number_of_clusters);
+    println!("{}: {} FATs of {} sectors, {} sectors per cluster, media {:02X}h", default_drive, dpb.fat_count,
+             dpb.sectors_per_fat, dpb.sectors_per_cluster, dpb.media_id_byte);
+}
+
+#[allow(dead_code)]
+pub(crate) fn logical_drive_count_test() {
+    let default_drive = get_default_drive();
+    let drive_count = logical_drive_count();
+    assert!(drive_count >= 2);
+    assert_eq!(get_default_drive(), default_drive);
+    assert_eq!(set_default_drive(default_drive), drive_count);
+    println!("{} logical drives", drive_count);
+}
+
+#[allow(dead_code)]
+pub(crate) fn drives_test() {
+    assert!(drives().any(|drive| drive == DriveLetter::C));
+    assert!(drives().all(|drive| get_allocation_info_for_specified_drive(drive).is_some()));
+    for drive in drives() {
+        print!("{}: ", drive);
+    }
+    println!("ready");
+}
+
+#[allow(dead_code)]
+pub(crate) fn allocation_info_test() {
+    let allocation_info = get_allocation_info_for_specified_drive(DriveLetter::C).unwrap();
+    let disk_space = get_free_disk_space(DriveLetter::C).unwrap();
+    assert_eq!(allocation_info.bytes_per_cluster(), disk_space.bytes_per_cluster);
+    assert_eq!(allocation_info.total_bytes(), disk_space.total_bytes);
+    println!("C: {} clusters of {} bytes", allocation_info.number_of_clusters, allocation_info.bytes_per_cluster());
+}
+
+#[allow(dead_code)]
+pub(crate) fn media_id_test() {
+    use dos::disk::{get_media_id, set_media_id};
+
+    let media_id = get_media_id(DriveLetter::C).unwrap();
+    assert_ne!(media_id.serial, 0);
+    assert_eq!(&media_id.fs_type[..3], b"FAT");
+    // Writing back the same identification changes nothing
+    set_media_id(DriveLetter::C, &media_id).unwrap();
+    assert_eq!(get_media_id(DriveLetter::C).unwrap(), media_id);
+    println!("C: serial {:04X}-{:04X}, label {}", media_id.serial >> 16, media_id.serial & 0xFFFF,
+             core::str::from_utf8(&media_id.label).unwrap_or("?"));
+}
+
+#[allow(dead_code)]
+pub(crate) fn volume_label_test() {
+    use dos::disk::{set_volume_label, volume_label};
+
+    let label = volume_label(DriveLetter::C).unwrap();
+    println!("C: label {:?}", label);
+    if let Some(label) = label {
+        set_volume_label(DriveLetter::C, "Rust_DOS 01").unwrap();
+        assert_eq!(volume_label(DriveLetter::C).unwrap().as_deref(), Some("RUST_DOS 01"));
+        set_volume_label(DriveLetter::C, &label).unwrap();
+        assert_eq!(volume_label(DriveLetter::C).unwrap(), Some(label));
+    }
+    assert!(set_volume_label(DriveLetter::C, "TWELVE CHARS").is_err());
+}
@@ -0,0 +1,23 @@
+use rust_dos::*;
+use ems::{allocate, is_installed, page_frame_segment, status, total_and_free_pages};
+
+#[allow(dead_code)]
+pub(crate) fn ems_test() {
+    if !is_installed() {
+        println!("No EMS driver");
+        return;
+    }
+    status().unwrap();
+    let (total, free) = total_and_free_pages();
+    println!("Page frame at {:04X}h, {} of {} pages free", page_frame_segment().unwrap(), free, total);
+    let handle = allocate(1).unwrap();
+    let page = handle.map_page(0, 0).unwrap();
+    unsafe {
+        page.as_mut_ptr::<u8>().write_volatile(0x5A);
+        assert_eq!(page.as_ptr::<u8>().read_volatile(), 0x5A);
+    }
+    assert_eq!(handle.map_page(1, 0).unwrap_err(), ems::EmsError::InvalidLogicalPage);
+    handle.free().unwrap();
+    assert_eq!(total_and_free_pages().1, free);
+    println!("Allocated, mapped and freed a page");
+}
@@ -0,0 +1,25 @@
+use rust_dos::*;
+use dos::env::{args, environment, getenv};
+
+/// Run as `RUST_DOS.COM hello  world` (note the two spaces).
+#[allow(dead_code)]
+pub(crate) fn args_test() {
+    let mut arguments = args();
+    assert_eq!(arguments.next(), Some("hello"));
+    assert_eq!(arguments.next(), Some("world"));
+    assert_eq!(arguments.next(), None);
+    assert_eq!(arguments.next(), None);
+    println!("Arguments: {}", args().count());
+}
+
+#[allow(dead_code)]
+pub(crate) fn environment_test() {
+    for (name, value) in environment() {
+        println!("{}={}", name, value);
+    }
+    // DOSBox sets PATH=Z:\ by default
+    let path = getenv("PATH").unwrap();
+    assert_eq!(getenv("path"), Some(path));
+    assert_eq!(getenv("NO_SUCH_VARIABLE"), None);
+    println!("PATH is {}", path);
+}
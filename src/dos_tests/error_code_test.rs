@@ -0,0 +1,70 @@
+use rust_dos::*;
+use dos::error_code::{get_extended_error, DetailedError, ErrorClass, ErrorCode};
+use dos::file::File;
+
+#[allow(dead_code)]
+pub(crate) fn extended_error_test() {
+    let error = File::open("C:\\NOFILE.XYZ").err().unwrap();
+    let extended_error = get_extended_error().unwrap();
+    assert!(matches!(error, ErrorCode::FileNotFound));
+    assert_eq!(extended_error.code, ErrorCode::FileNotFound as u16);
+    assert_eq!(extended_error.class, ErrorClass::NotFound);
+    println!("Extended error: {:?}, {:?}, {:?}", extended_error.class, extended_error.action, extended_error.locus);
+
+    let detailed_error = File::open("C:\\NOFILE.XYZ").err().map(DetailedError::from).unwrap();
+    println!("Detailed error: {}", detailed_error);
+}
+
+#[allow(dead_code)]
+pub(crate) fn with_retry_test() {
+    use dos::error_code::with_retry;
+
+    let mut calls = 0;
+    let result = with_retry(3, || {
+        calls += 1;
+        if calls < 3 { Err(ErrorCode::DriveNotReady) } else { Ok(calls) }
+    });
+    assert_eq!(result, Ok(3));
+
+    calls = 0;
+    let result: Result<(), _> = with_retry(3, || {
+        calls += 1;
+        Err(ErrorCode::ReadFault)
+    });
+    assert_eq!(result, Err(ErrorCode::ReadFault));
+    assert_eq!(calls, 3);
+
+    // Not retried
+    calls = 0;
+    let result: Result<(), _> = with_retry(3, || {
+        calls += 1;
+        Err(ErrorCode::FileNotFound)
+    });
+    assert_eq!(result, Err(ErrorCode::FileNotFound));
+    assert_eq!(calls, 1);
+    println!("Transient errors retried");
+}
+
+/// Manual: A: must exist with no disk in it, such as an empty floppy drive.
+/// Insert a disk with DATA.TXT within 2 seconds to see the retry succeed.
+#[allow(dead_code)]
+pub(crate) fn with_retry_removable_test() {
+    use dos::critical_error::{install_critical_error_handler, uninstall_critical_error_handler, CriticalResponse};
+    use dos::error_code::with_retry;
+
+    install_critical_error_handler(|_| CriticalResponse::Fail);
+    let mut calls = 0;
+    let result = with_retry(5, || {
+        calls += 1;
+        File::open("A:\\DATA.TXT")
+    });
+    uninstall_critical_error_handler();
+    match result {
+        Ok(_) => println!("A:\\DATA.TXT opened after {} attempts", calls),
+        Err(error) => {
+            assert_eq!(error, ErrorCode::FailOnInterrupt24h);
+            assert_eq!(calls, 5);
+            println!("Gave up after {} attempts: {}", calls, error);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use rust_dos::*;
+use dos::env::{args, psp_segment};
+use dos::error_code::ErrorCode;
+use dos::Vec;
+use psp::Psp;
+
+/// Use as the program entry with `entry!(exit_code_test)`,
+/// then check the return code from a batch file with `IF ERRORLEVEL 3`.
+#[allow(dead_code)]
+pub(crate) fn exit_code_test() -> u8 {
+    3
+}
+
+/// Use as the program entry with `entry!(entry_setup_test)`: relies only on the startup code
+/// for the PSP, the heap and the standard output. The return code is 0 on success.
+#[allow(dead_code)]
+pub(crate) fn entry_setup_test() -> Result<(), ErrorCode> {
+    assert_eq!(psp_segment(), Psp::current().segment());
+    let mut squares = Vec::new();
+    for i in 0..1000_u32 {
+        squares.push(i * i);
+    }
+    assert_eq!(squares.iter().sum::<u32>(), 332_833_500);
+    let arguments: Vec<&str> = args().collect();
+    println!("{} arguments: {:?}", arguments.len(), arguments);
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use rust_dos::*;
+use dos::disk::DriveLetter;
+use dos::file::File;
+use fat::FatVolume;
+
+/// C: must be a FAT drive, such as a DOSBox `imgmount`ed image, not a mounted directory.
+#[allow(dead_code)]
+pub(crate) fn fat_read_file_test() {
+    let file = File::create("C:\\FATTEST.TXT").unwrap();
+    file.write(b"Read from the FAT\r\n").unwrap();
+    file.close().unwrap();
+
+    let volume = FatVolume::open(DriveLetter::C).unwrap();
+    let fat_data = volume.read_file("C:\\FATTEST.TXT").unwrap();
+    let mut buffer = [0; 64];
+    let file = File::open("C:\\FATTEST.TXT").unwrap();
+    let bytes_read = file.read(&mut buffer).unwrap();
+    assert_eq!(fat_data.as_slice(), &buffer[..bytes_read]);
+    assert!(volume.root_directory().unwrap().iter().any(|entry| entry.name() == "FATTEST.TXT"));
+    println!("{:?} file read, {} bytes", volume.fat_type(), fat_data.len());
+
+    // Several clusters, and several reads per cluster on large cluster drives
+    let mut data = [0u8; 20000];
+    crate::dos_tests::fill_test_pattern(&mut data);
+    dos::file::write("C:\\FATBIG.BIN", &data).unwrap();
+    let fat_data = volume.read_file("C:\\FATBIG.BIN").unwrap();
+    assert!(fat_data == data);
+    dos::file::remove_file("C:\\FATBIG.BIN").unwrap();
+    println!("{} bytes read across clusters", fat_data.len());
+}
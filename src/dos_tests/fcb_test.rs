@@ -0,0 +1,84 @@
+use rust_dos::*;
+use dos::fcb::{close_file, get_file_size_in_records, open_file, parse_filename, random_block_read, random_read,
+               random_write, sequential_read, set_disk_transfer_address, set_random_record_number, FcbIoResult,
+               FcbResult, ParseFlags, ParseResult};
+use dos::file::File;
+
+#[allow(dead_code)]
+pub(crate) fn parse_filename_test() {
+    let (fcb, result) = parse_filename("B:FOO.TXT", ParseFlags::NONE).unwrap();
+    // B: may not be mounted
+    assert!(result == ParseResult::NoWildcards || result == ParseResult::InvalidDrive);
+    assert_eq!(fcb.drive, 2);
+    assert_eq!(fcb.name(), "FOO");
+    assert_eq!(fcb.extension(), "TXT");
+
+    let (fcb, result) = parse_filename("  *.*", ParseFlags::SKIP_LEADING_SEPARATORS).unwrap();
+    assert_eq!(result, ParseResult::Wildcards);
+    assert_eq!(fcb.drive, 0);
+    assert_eq!(fcb.name(), "????????");
+    assert_eq!(fcb.extension(), "???");
+    println!("Filenames parsed");
+}
+
+#[allow(dead_code)]
+pub(crate) fn random_block_read_test() {
+    let (mut fcb, _) = parse_filename("C:AUTOEXEC.BAT", ParseFlags::NONE).unwrap();
+    assert_eq!(open_file(&mut fcb), FcbResult::Success);
+    let mut dta = [0_u8; 128];
+    set_disk_transfer_address(&mut dta);
+    fcb.record_size = 8;
+    fcb.random_record = 0;
+    let (status, records_read) = random_block_read(&mut fcb, 10);
+    assert_eq!(status, FcbIoResult::Success);
+    assert_eq!(records_read, 10);
+    assert_eq!({ fcb.random_record }, 10);
+    assert_eq!(close_file(&mut fcb), FcbResult::Success);
+    println!("{}", core::str::from_utf8(&dta[..80]).unwrap_or("(not text)"));
+}
+
+#[allow(dead_code)]
+pub(crate) fn fcb_open_failure_test() {
+    let (mut fcb, _) = parse_filename("C:NOSUCH.XYZ", ParseFlags::NONE).unwrap();
+    assert_eq!(open_file(&mut fcb), FcbResult::Failure);
+    println!("Missing file not opened");
+}
+
+#[allow(dead_code)]
+pub(crate) fn fcb_random_read_test() {
+    let file = File::create("C:\\RANDOM.DAT").unwrap();
+    file.write(b"0123456789ABCDEF0123456789ABCDEFtail").unwrap();
+    file.close().unwrap();
+
+    let (mut fcb, _) = parse_filename("C:RANDOM.DAT", ParseFlags::NONE).unwrap();
+    fcb.record_size = 16;
+    assert_eq!(get_file_size_in_records(&mut fcb), Some(3));
+    assert_eq!(open_file(&mut fcb), FcbResult::Success);
+    let mut dta = [0_u8; 16];
+    set_disk_transfer_address(&mut dta);
+    fcb.record_size = 16;
+
+    fcb.random_record = 1;
+    assert_eq!(random_read(&mut fcb), FcbIoResult::Success);
+    assert_eq!(&dta, b"0123456789ABCDEF");
+    assert_eq!({ fcb.random_record }, 1);
+    fcb.random_record = 2;
+    assert_eq!(random_read(&mut fcb), FcbIoResult::PartialRecord);
+    assert_eq!(&dta[..4], b"tail");
+    assert!(dta[4..].iter().all(|&c| c == 0));
+    fcb.random_record = 3;
+    assert_eq!(random_read(&mut fcb), FcbIoResult::Eof);
+
+    dta.copy_from_slice(b"fedcba9876543210");
+    fcb.random_record = 0;
+    assert_eq!(random_write(&mut fcb), FcbIoResult::Success);
+    assert_eq!(sequential_read(&mut fcb), FcbIoResult::Success);
+    set_random_record_number(&mut fcb);
+    assert_eq!({ fcb.random_record }, 1);
+    assert_eq!(close_file(&mut fcb), FcbResult::Success);
+
+    let (mut fcb, _) = parse_filename("C:NOSUCH.XYZ", ParseFlags::NONE).unwrap();
+    fcb.record_size = 16;
+    assert_eq!(get_file_size_in_records(&mut fcb), None);
+    println!("FCB random records read and written");
+}
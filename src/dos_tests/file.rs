@@ -12,4 +12,412 @@ pub(crate) fn file_read_test() {
         Ok(_) => println!("File closed"),
         Err(_) => println!("Error closing file")
     }
-}
\ No newline at end of file
+}
+
+#[allow(dead_code)]
+pub(crate) fn file_attributes_test() {
+    use dos::file::{get_attributes, set_attributes, FileAttributes};
+    use dos::error_code::ErrorCode;
+
+    let path = "C:\\AUTOEXEC.BAT";
+    let original_attributes = get_attributes(path).unwrap();
+    set_attributes(path, original_attributes | FileAttributes::READ_ONLY).unwrap();
+    assert!(get_attributes(path).unwrap().contains(FileAttributes::READ_ONLY));
+    set_attributes(path, original_attributes.remove(FileAttributes::READ_ONLY)).unwrap();
+    assert!(!get_attributes(path).unwrap().contains(FileAttributes::READ_ONLY));
+    assert!(matches!(set_attributes(path, FileAttributes::DIRECTORY), Err(ErrorCode::InvalidData)));
+    set_attributes(path, original_attributes).unwrap();
+    println!("File attributes toggled");
+}
+
+#[allow(dead_code)]
+pub(crate) fn file_modified_test() {
+    use dos::datetime::{Date, Time};
+
+    let file = dos::file::File::open("C:\\AUTOEXEC.BAT").unwrap();
+    let (original_date, original_time) = file.modified().unwrap();
+    println!("Modified on {}-{}-{} {}:{}:{}", original_date.year, original_date.month, original_date.day,
+             original_time.hour, original_time.minute, original_time.second);
+    let date = Date::new(1994, 7, 21);
+    let time = Time::new(13, 37, 42);
+    file.set_modified(date, time).unwrap();
+    assert_eq!(file.modified().unwrap(), (date, time));
+    file.set_modified(original_date, original_time).unwrap();
+    assert_eq!(file.modified().unwrap(), (original_date, original_time));
+    println!("File date and time set");
+}
+
+/// SHARE.EXE must be loaded.
+#[allow(dead_code)]
+pub(crate) fn file_lock_test() {
+    use dos::error_code::ErrorCode;
+
+    let file = dos::file::File::open("C:\\AUTOEXEC.BAT").unwrap();
+    file.lock(0, 16).unwrap();
+    let overlapping_lock = file.lock(8, 16);
+    assert!(matches!(overlapping_lock, Err(ErrorCode::LockViolation) | Err(ErrorCode::AccessDenied)));
+    file.unlock(0, 16).unwrap();
+    file.lock(8, 16).unwrap();
+    file.unlock(8, 16).unwrap();
+    println!("File region locked and unlocked");
+}
+
+#[allow(dead_code)]
+pub(crate) fn stdio_redirect_test() {
+    use dos::file::{File, StdioRedirect};
+
+    let file = File::create("REDIRECT.TXT").unwrap();
+    let redirect = StdioRedirect::new(&file).unwrap();
+    println!("Redirected");
+    drop(redirect);
+    file.close().unwrap();
+
+    let file = File::open("REDIRECT.TXT").unwrap();
+    let mut buffer = [0; 32];
+    let bytes_read = file.read(&mut buffer).unwrap();
+    assert_eq!(&buffer[..bytes_read], b"Redirected\r\n");
+    println!("Standard output redirected and restored");
+}
+
+#[allow(dead_code)]
+pub(crate) fn handle_count_test() {
+    use dos::file::{set_handle_count, File};
+    use dos::Vec;
+
+    set_handle_count(40).unwrap();
+    let mut files = Vec::new();
+    for _ in 0..30 {
+        files.push(File::open("C:\\AUTOEXEC.BAT").unwrap());
+    }
+    println!("{} files open", files.len());
+}
+
+#[allow(dead_code)]
+pub(crate) fn file_commit_test() {
+    use dos::file::File;
+
+    let file = File::create("COMMIT.TXT").unwrap();
+    file.write(b"Committed").unwrap();
+    file.commit().unwrap();
+    let second_handle = File::open("COMMIT.TXT").unwrap();
+    let mut buffer = [0; 16];
+    let bytes_read = second_handle.read(&mut buffer).unwrap();
+    assert_eq!(&buffer[..bytes_read], b"Committed");
+    println!("File committed without closing");
+}
+
+/// CREATNEW.TXT must not exist.
+#[allow(dead_code)]
+pub(crate) fn file_create_new_test() {
+    use dos::error_code::ErrorCode;
+    use dos::file::{File, FileAttributes};
+
+    let file = File::create_new("CREATNEW.TXT", FileAttributes::NORMAL).unwrap();
+    assert!(matches!(File::create_new("CREATNEW.TXT", FileAttributes::NORMAL), Err(ErrorCode::FileAlreadyExists)));
+    file.close().unwrap();
+    println!("File created only once");
+}
+
+#[allow(dead_code)]
+pub(crate) fn file_create_temp_test() {
+    use dos::file::File;
+
+    let (first_file, first_path) = File::create_temp("C:\\").unwrap();
+    let (second_file, second_path) = File::create_temp("C:\\").unwrap();
+    assert!(first_path.starts_with("C:\\"));
+    assert_ne!(first_path, second_path);
+    first_file.close().unwrap();
+    second_file.close().unwrap();
+    println!("Temporary files {} and {} created", first_path, second_path);
+
+    let long_directory = core::str::from_utf8(&[b'A'; 80]).unwrap();
+    assert_eq!(File::create_temp(long_directory).err(), Some(dos::error_code::ErrorCode::PathNotFound));
+}
+
+#[allow(dead_code)]
+pub(crate) fn buf_reader_test() {
+    use bios::timer::{read_tick_count, ticks_to_millis};
+    use dos::file::{BufReader, File};
+    use dos::{String, Vec};
+
+    let file = File::create("LINES.TXT").unwrap();
+    file.write(b"first\r\nsecond\r\n\r\nlast").unwrap();
+    file.close().unwrap();
+    let lines: Vec<String> = BufReader::new(File::open("LINES.TXT").unwrap()).lines().map(|line| line.unwrap()).collect();
+    assert_eq!(lines, ["first", "second", "", "last"]);
+
+    let start = read_tick_count();
+    let file = File::open("C:\\AUTOEXEC.BAT").unwrap();
+    let mut unbuffered_count = 0;
+    let mut buffer = [0; 1];
+    while file.read(&mut buffer).unwrap() != 0 {
+        unbuffered_count += 1;
+    }
+    let unbuffered_ticks = read_tick_count() - start;
+
+    let start = read_tick_count();
+    let mut reader = BufReader::new(File::open("C:\\AUTOEXEC.BAT").unwrap());
+    let mut buffered_count = 0;
+    while reader.read_byte().unwrap().is_some() {
+        buffered_count += 1;
+    }
+    let buffered_ticks = read_tick_count() - start;
+
+    assert_eq!(buffered_count, unbuffered_count);
+    println!("{} bytes read in {} ms unbuffered, {} ms buffered", buffered_count,
+             ticks_to_millis(unbuffered_ticks), ticks_to_millis(buffered_ticks));
+}
+
+#[allow(dead_code)]
+pub(crate) fn buf_writer_test() {
+    use core::fmt::Write;
+    use dos::file::{BufWriter, File, SeekFrom};
+
+    let mut writer = BufWriter::new(File::create("BUFWRITE.TXT").unwrap());
+    let mut expected_size: u32 = 0;
+    for i in 0..10000 {
+        writeln!(writer, "Line {}\r", i).unwrap();
+        let digits = if i < 10 { 1 } else if i < 100 { 2 } else if i < 1000 { 3 } else { 4 };
+        expected_size += "Line \r\n".len() as u32 + digits;
+    }
+    writer.into_inner().unwrap().close().unwrap();
+
+    let file = File::open("BUFWRITE.TXT").unwrap();
+    assert_eq!(file.seek(SeekFrom::End(0)).unwrap(), expected_size);
+    println!("{} bytes written", expected_size);
+}
+
+#[allow(dead_code)]
+pub(crate) fn flush_all_files_test() {
+    use dos::file::{flush_all_files, File, SeekFrom};
+
+    let first_file = File::create("FLUSH1.TXT").unwrap();
+    let second_file = File::create("FLUSH2.TXT").unwrap();
+    first_file.write(b"First").unwrap();
+    second_file.write(b"Second file").unwrap();
+    flush_all_files(&[&first_file, &second_file]).unwrap();
+    assert_eq!(File::open("FLUSH1.TXT").unwrap().seek(SeekFrom::End(0)).unwrap(), 5);
+    assert_eq!(File::open("FLUSH2.TXT").unwrap().seek(SeekFrom::End(0)).unwrap(), 11);
+    println!("Files flushed");
+}
+
+#[allow(dead_code)]
+pub(crate) fn file_size_test() {
+    use dos::error_code::ErrorCode;
+    use dos::file::{file_size, File, SeekFrom};
+
+    let size = file_size("C:\\AUTOEXEC.BAT").unwrap();
+    assert_eq!(File::open("C:\\AUTOEXEC.BAT").unwrap().seek(SeekFrom::End(0)).unwrap(), size);
+    assert!(matches!(file_size("C:\\NOSUCH.XYZ"), Err(ErrorCode::FileNotFound)));
+    assert!(matches!(file_size("C:\\*.BAT"), Err(ErrorCode::FileNotFound)));
+    dos::directory::make_directory("C:\\SIZEDIR").unwrap();
+    assert!(matches!(file_size("C:\\SIZEDIR"), Err(ErrorCode::AccessDenied)));
+    dos::directory::remove_directory("C:\\SIZEDIR").unwrap();
+    assert!(matches!(file_size("C:\\SIZEDIR"), Err(ErrorCode::FileNotFound)));
+    println!("AUTOEXEC.BAT is {} bytes", size);
+}
+
+#[allow(dead_code)]
+pub(crate) fn rename_remove_test() {
+    use dos::error_code::ErrorCode;
+    use dos::file::{file_size, remove_file, rename, set_attributes, File, FileAttributes};
+
+    let file = File::create("C:\\OLDNAME.TXT").unwrap();
+    file.write(b"Renamed").unwrap();
+    file.close().unwrap();
+    rename("C:\\OLDNAME.TXT", "C:\\NEWNAME.TXT").unwrap();
+    assert!(matches!(file_size("C:\\OLDNAME.TXT"), Err(ErrorCode::FileNotFound)));
+    assert_eq!(file_size("C:\\NEWNAME.TXT").unwrap(), 7);
+    // Z: is the drive of DOSBox
+    assert!(matches!(rename("C:\\NEWNAME.TXT", "Z:\\NEWNAME.TXT"), Err(ErrorCode::NotSameDevice)));
+
+    set_attributes("C:\\NEWNAME.TXT", FileAttributes::READ_ONLY).unwrap();
+    assert!(matches!(remove_file("C:\\NEWNAME.TXT"), Err(ErrorCode::AccessDenied)));
+    set_attributes("C:\\NEWNAME.TXT", FileAttributes::NORMAL).unwrap();
+    remove_file("C:\\NEWNAME.TXT").unwrap();
+    assert!(matches!(file_size("C:\\NEWNAME.TXT"), Err(ErrorCode::FileNotFound)));
+    assert!(matches!(remove_file("C:\\NEWNAME.TXT"), Err(ErrorCode::FileNotFound)));
+    println!("File renamed and removed");
+}
+
+#[allow(dead_code)]
+pub(crate) fn metadata_test() {
+    use dos::directory::{make_directory, remove_directory};
+    use dos::error_code::ErrorCode;
+    use dos::file::{get_attributes, metadata, File, SeekFrom};
+
+    let file = File::open("C:\\AUTOEXEC.BAT").unwrap();
+    let file_metadata = metadata("C:\\AUTOEXEC.BAT").unwrap();
+    assert!(!file_metadata.is_dir);
+    assert_eq!(file_metadata.size, file.seek(SeekFrom::End(0)).unwrap());
+    assert_eq!(file_metadata.modified, file.modified().unwrap());
+    assert_eq!(file_metadata.attributes, get_attributes("C:\\AUTOEXEC.BAT").unwrap());
+
+    make_directory("C:\\METADIR").unwrap();
+    let directory_metadata = metadata("C:\\METADIR").unwrap();
+    remove_directory("C:\\METADIR").unwrap();
+    assert!(directory_metadata.is_dir);
+    assert_eq!(directory_metadata.size, 0);
+    assert_eq!(metadata("C:\\METADIR").err(), Some(ErrorCode::FileNotFound));
+    assert_eq!(metadata("C:\\NOSUCH.XYZ").err(), Some(ErrorCode::FileNotFound));
+    println!("AUTOEXEC.BAT: {:?}", file_metadata);
+}
+
+#[allow(dead_code)]
+pub(crate) fn copy_test() {
+    use alloc::vec;
+    use dos::file::{copy, metadata, read, remove_file, write};
+
+    let bytes_copied = copy("C:\\AUTOEXEC.BAT", "C:\\COPY.BAT").unwrap();
+    let source = metadata("C:\\AUTOEXEC.BAT").unwrap();
+    let destination = metadata("C:\\COPY.BAT").unwrap();
+    assert_eq!(bytes_copied, source.size);
+    assert_eq!(destination.size, source.size);
+    assert_eq!(destination.modified, source.modified);
+    remove_file("C:\\COPY.BAT").unwrap();
+    println!("{} bytes copied", bytes_copied);
+
+    // Several buffers
+    let mut data = vec![0u8; 20000];
+    crate::dos_tests::fill_test_pattern(&mut data);
+    write("C:\\COPYSRC.BIN", &data).unwrap();
+    assert_eq!(copy("C:\\COPYSRC.BIN", "C:\\COPYDST.BIN").unwrap(), 20000);
+    assert!(read("C:\\COPYDST.BIN").unwrap() == data);
+    remove_file("C:\\COPYSRC.BIN").unwrap();
+    remove_file("C:\\COPYDST.BIN").unwrap();
+}
+
+#[allow(dead_code)]
+pub(crate) fn write_all_read_exact_test() {
+    use dos::file::{remove_file, File};
+    use dos::error_code::ErrorCode;
+
+    let mut data = [0u8; 3000];
+    crate::dos_tests::fill_test_pattern(&mut data);
+    let mut file = File::create("C:\\EXACT.BIN").unwrap();
+    file.write_all(&data).unwrap();
+    file.close().unwrap();
+
+    let mut file = File::open("C:\\EXACT.BIN").unwrap();
+    let mut buffer = [0u8; 3000];
+    file.read_exact(&mut buffer).unwrap();
+    assert!(buffer == data);
+    let mut past_end = [0u8; 1];
+    assert_eq!(file.read_exact(&mut past_end), Err(ErrorCode::CannotCompleteFileOperation));
+    file.close().unwrap();
+    remove_file("C:\\EXACT.BIN").unwrap();
+    println!("{} bytes written and read back", data.len());
+}
+
+/// Manual: run with the standard input redirected, as `PROGRAM < C:\AUTOEXEC.BAT`.
+#[allow(dead_code)]
+pub(crate) fn read_stdin_to_end_test() {
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use dos::file::{file_size, read_stdin_to_end, File};
+
+    let mut input = Vec::new();
+    let bytes_read = read_stdin_to_end(&mut input).unwrap();
+    assert_eq!(bytes_read, input.len());
+    let mut expected = vec![0u8; file_size("C:\\AUTOEXEC.BAT").unwrap() as usize];
+    File::open("C:\\AUTOEXEC.BAT").unwrap().read_exact(&mut expected).unwrap();
+    assert!(input == expected);
+    // Still at the end
+    assert_eq!(read_stdin_to_end(&mut input), Ok(0));
+    println!("{} bytes read from the standard input", bytes_read);
+}
+
+#[allow(dead_code)]
+pub(crate) fn stdin_redirect_test() {
+    use alloc::vec::Vec;
+    use dos::error_code::ErrorCode;
+    use dos::file::{read_stdin_to_end, File, StdinRedirect, STDIN};
+    use dos::ioctl::get_device_info;
+
+    let mut file = File::create("REDIRECT.TXT").unwrap();
+    file.write_all(b"first line\r\nsecond line\r\n").unwrap();
+    file.close().unwrap();
+
+    let redirect = StdinRedirect::new("REDIRECT.TXT").unwrap();
+    assert!(!get_device_info(STDIN).unwrap().is_device());
+    let mut input = Vec::new();
+    read_stdin_to_end(&mut input).unwrap();
+    drop(redirect);
+    assert!(get_device_info(STDIN).unwrap().is_console());
+    let lines: Vec<&[u8]> = input.split(|&c| c == b'\n').map(|line| line.strip_suffix(b"\r").unwrap_or(line)).collect();
+    assert_eq!(lines, [&b"first line"[..], b"second line", b""]);
+
+    assert_eq!(StdinRedirect::new("NOFILE.XYZ").err(), Some(ErrorCode::FileNotFound));
+    println!("Standard input redirected and restored");
+}
+
+#[allow(dead_code)]
+pub(crate) fn read_whole_file_test() {
+    use dos::file::{read, read_to_string, remove_file, File};
+
+    let mut file = File::create("READ.TXT").unwrap();
+    file.write_all(b"Hello\r\nDOS\r\n").unwrap();
+    file.close().unwrap();
+    assert_eq!(read("READ.TXT").unwrap(), b"Hello\r\nDOS\r\n");
+    assert_eq!(read_to_string("READ.TXT").unwrap(), "Hello\r\nDOS\r\n");
+
+    // CP437 box drawing, not UTF-8
+    let mut file = File::create("READ.TXT").unwrap();
+    file.write_all(&[0xC9, 0xCD, 0xBB]).unwrap();
+    file.close().unwrap();
+    assert_eq!(read_to_string("READ.TXT").unwrap(), "╔═╗");
+    remove_file("READ.TXT").unwrap();
+    assert!(read("NOFILE.XYZ").is_err());
+    println!("Whole files read");
+}
+
+#[allow(dead_code)]
+pub(crate) fn write_whole_file_test() {
+    use dos::file::{file_size, read, read_to_string, remove_file, write, write_str};
+
+    let mut data = [0u8; 1500];
+    crate::dos_tests::fill_test_pattern(&mut data);
+    write("WRITE.BIN", &data).unwrap();
+    assert!(read("WRITE.BIN").unwrap() == data);
+    write_str("WRITE.BIN", "Replaced\r\n").unwrap();
+    assert_eq!(read_to_string("WRITE.BIN").unwrap(), "Replaced\r\n");
+    write("WRITE.BIN", &[]).unwrap();
+    assert_eq!(file_size("WRITE.BIN").unwrap(), 0);
+    assert!(read("WRITE.BIN").unwrap().is_empty());
+    remove_file("WRITE.BIN").unwrap();
+    println!("Whole files written");
+}
+
+#[allow(dead_code)]
+pub(crate) fn open_options_test() {
+    use dos::error_code::ErrorCode;
+    use dos::file::{read, remove_file, OpenOptions};
+
+    let _ = remove_file("APPEND.TXT");
+    let mut file = OpenOptions::new().append(true).create(true).open("APPEND.TXT").unwrap();
+    file.write_all(b"first ").unwrap();
+    file.close().unwrap();
+    let mut file = OpenOptions::new().append(true).create(true).open("APPEND.TXT").unwrap();
+    file.write_all(b"second").unwrap();
+    file.close().unwrap();
+    assert_eq!(read("APPEND.TXT").unwrap(), b"first second");
+
+    let mut file = OpenOptions::new().write(true).truncate(true).open("APPEND.TXT").unwrap();
+    file.write_all(b"third").unwrap();
+    file.close().unwrap();
+    assert_eq!(read("APPEND.TXT").unwrap(), b"third");
+
+    assert_eq!(OpenOptions::new().write(true).create_new(true).open("APPEND.TXT").err(), Some(ErrorCode::FileAlreadyExists));
+    remove_file("APPEND.TXT").unwrap();
+    let file = OpenOptions::new().write(true).create_new(true).open("APPEND.TXT").unwrap();
+    file.close().unwrap();
+    assert_eq!(OpenOptions::new().read(true).write(true).open("NOFILE.XYZ").err(), Some(ErrorCode::FileNotFound));
+
+    // Conflicting options
+    assert_eq!(OpenOptions::new().open("APPEND.TXT").err(), Some(ErrorCode::InvalidAccessCode));
+    assert_eq!(OpenOptions::new().read(true).truncate(true).open("APPEND.TXT").err(), Some(ErrorCode::InvalidAccessCode));
+    assert_eq!(OpenOptions::new().read(true).create(true).open("APPEND.TXT").err(), Some(ErrorCode::InvalidAccessCode));
+    assert_eq!(OpenOptions::new().append(true).truncate(true).open("APPEND.TXT").err(), Some(ErrorCode::InvalidAccessCode));
+    remove_file("APPEND.TXT").unwrap();
+    println!("Open options OK");
+}
@@ -0,0 +1,85 @@
+use core::arch::{asm, global_asm};
+use rust_dos::*;
+use dos::far_ptr::FarPtr;
+use dos::interrupt_vector::{get_interrupt_vector, set_interrupt_vector};
+
+static mut TICKS: u32 = 0;
+
+// INT 1Ch is called by the BIOS timer handler about 18.2 times per second
+global_asm!(
+    ".global tick_counter_handler",
+    "tick_counter_handler:",
+    "inc dword ptr cs:[{ticks}]",
+    "iret",
+    ticks = sym TICKS,
+);
+
+extern "C" {
+    fn tick_counter_handler();
+}
+
+#[allow(dead_code)]
+pub(crate) fn interrupt_vector_test() {
+    let code_segment: u16;
+    unsafe { asm!("mov {0:x}, cs", out(reg) code_segment) }
+    let handler = FarPtr::new(code_segment, tick_counter_handler as *const () as usize as u16);
+
+    let old_vector = get_interrupt_vector(0x1C);
+    set_interrupt_vector(0x1C, handler);
+    assert_eq!(get_interrupt_vector(0x1C), handler);
+    while unsafe { core::ptr::addr_of!(TICKS).read_volatile() } < 18 {}
+    set_interrupt_vector(0x1C, old_vector);
+    assert_eq!(get_interrupt_vector(0x1C), old_vector);
+    println!("Counted {} ticks", unsafe { core::ptr::addr_of!(TICKS).read_volatile() });
+}
+
+static mut HANDLER_TICKS: u32 = 0;
+
+fn count_tick() {
+    unsafe { core::ptr::addr_of_mut!(HANDLER_TICKS).write_volatile(core::ptr::addr_of!(HANDLER_TICKS).read_volatile() + 1) }
+}
+
+#[allow(dead_code)]
+pub(crate) fn interrupt_handler_test() {
+    let old_vector = get_interrupt_vector(0x1C);
+    set_interrupt_vector(0x1C, interrupt_handler!(count_tick));
+    while unsafe { core::ptr::addr_of!(HANDLER_TICKS).read_volatile() } < 18 {}
+    set_interrupt_vector(0x1C, old_vector);
+    println!("Counted {} ticks", unsafe { core::ptr::addr_of!(HANDLER_TICKS).read_volatile() });
+}
+
+static mut LONG_TICKS: u64 = 0;
+
+fn count_long_tick() {
+    unsafe { core::ptr::addr_of_mut!(LONG_TICKS).write_volatile(core::ptr::addr_of!(LONG_TICKS).read_volatile() + 1) }
+}
+
+fn interrupts_enabled() -> bool {
+    let flags: u16;
+    unsafe { asm!("pushf", "pop {flags:x}", flags = out(reg) flags) }
+    flags & 0x0200 != 0
+}
+
+#[allow(dead_code)]
+pub(crate) fn critical_section_test() {
+    use dos::interrupt_vector::{critical_section, InterruptGuard};
+
+    let old_vector = get_interrupt_vector(0x1C);
+    set_interrupt_vector(0x1C, interrupt_handler!(count_long_tick));
+    assert!(interrupts_enabled());
+    let mut ticks = 0;
+    while ticks < 18 {
+        // A u64 is read with several instructions, the handler must not run in between
+        ticks = critical_section(|| {
+            assert!(!interrupts_enabled());
+            {
+                let _nested = InterruptGuard::new();
+            }
+            assert!(!interrupts_enabled());
+            unsafe { core::ptr::addr_of!(LONG_TICKS).read_volatile() }
+        });
+        assert!(interrupts_enabled());
+    }
+    set_interrupt_vector(0x1C, old_vector);
+    println!("Counted {} ticks in critical sections", ticks);
+}
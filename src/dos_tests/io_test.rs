@@ -0,0 +1,11 @@
+use rust_dos::*;
+use dos::io::inb;
+use dos::kbc::{kbc_status, KBC_CTRL, KBC_SYS_FLAG};
+
+#[allow(dead_code)]
+pub(crate) fn port_read_test() {
+    // Reading the status of the keyboard controller has no side effect
+    let status = unsafe { inb(KBC_CTRL) };
+    assert_eq!(status & KBC_SYS_FLAG, kbc_status() & KBC_SYS_FLAG);
+    println!("Keyboard controller status: {:#04x}", status);
+}
@@ -0,0 +1,16 @@
+use rust_dos::*;
+use dos::file::STDIN;
+use dos::ioctl::{get_device_info, set_raw_mode};
+
+/// Run without redirecting STDIN.
+#[allow(dead_code)]
+pub(crate) fn device_info_test() {
+    let info = get_device_info(STDIN).unwrap();
+    assert!(info.is_device());
+    assert!(info.is_console());
+    set_raw_mode(STDIN, true).unwrap();
+    assert!(get_device_info(STDIN).unwrap().is_raw());
+    set_raw_mode(STDIN, info.is_raw()).unwrap();
+    assert_eq!(get_device_info(STDIN).unwrap(), info);
+    println!("STDIN device info: {:#06x}", info.bits());
+}
@@ -0,0 +1,77 @@
+use rust_dos::*;
+use bios::keyboard::{key_available, read_key, shift_status, ShiftFlags};
+
+#[allow(dead_code)]
+pub(crate) fn keyboard_test() {
+    println!("Press keys, ESC to stop");
+    loop {
+        if !key_available() {
+            continue;
+        }
+        let key = read_key();
+        let shift = shift_status();
+        println!("Scancode: {:#04x}, ASCII: {:#04x}, shift: {}, ctrl: {}, alt: {}", key.scancode, key.ascii,
+                 shift.intersects(ShiftFlags::SHIFT), shift.contains(ShiftFlags::CTRL), shift.contains(ShiftFlags::ALT));
+        if key.scancode == 0x01 {
+            break;
+        }
+    }
+}
+
+/// Needs an arrow key press.
+#[allow(dead_code)]
+pub(crate) fn peek_key_test() {
+    use bios::keyboard::{peek_key, Key, KeyPress};
+
+    while peek_key().is_some() {
+        read_key();
+    }
+    assert_eq!(KeyPress { scancode: 0x48, ascii: 0x00 }.key(), Key::Up);
+    assert_eq!(KeyPress { scancode: 0x4B, ascii: 0xE0 }.key(), Key::Left);
+    assert_eq!(KeyPress { scancode: 0x3C, ascii: 0x00 }.key(), Key::Function(2));
+    assert_eq!(KeyPress { scancode: 0x1E, ascii: b'a' }.key(), Key::Char(b'a'));
+    assert_eq!(KeyPress { scancode: 0x1C, ascii: 0x0D }.key(), Key::Enter);
+
+    println!("Press an arrow key");
+    let peeked = loop {
+        if let Some(key) = peek_key() {
+            break key;
+        }
+    };
+    assert_eq!(peek_key(), Some(peeked));
+    let key = read_key();
+    assert_eq!(key, peeked);
+    assert!(matches!(key.key(), Key::Up | Key::Down | Key::Left | Key::Right));
+    println!("{:?} peeked, then read", key.key());
+}
+
+#[allow(dead_code)]
+pub(crate) fn translate_test() {
+    use bios::keyboard::translate;
+
+    let none = ShiftFlags::from_bits(0);
+    assert_eq!(translate(0x1E, none), Some('a'));
+    assert_eq!(translate(0x1E, ShiftFlags::LEFT_SHIFT), Some('A'));
+    assert_eq!(translate(0x1E, ShiftFlags::CAPS_LOCK), Some('A'));
+    assert_eq!(translate(0x1E, ShiftFlags::RIGHT_SHIFT | ShiftFlags::CAPS_LOCK), Some('a'));
+    // Caps Lock only affects letters
+    assert_eq!(translate(0x02, ShiftFlags::CAPS_LOCK), Some('1'));
+    assert_eq!(translate(0x02, ShiftFlags::LEFT_SHIFT), Some('!'));
+    assert_eq!(translate(0x28, ShiftFlags::LEFT_SHIFT), Some('"'));
+    assert_eq!(translate(0x2B, none), Some('\\'));
+    assert_eq!(translate(0x39, ShiftFlags::LEFT_SHIFT), Some(' '));
+    // Keypad
+    assert_eq!(translate(0x47, none), None);
+    assert_eq!(translate(0x47, ShiftFlags::NUM_LOCK), Some('7'));
+    assert_eq!(translate(0x53, ShiftFlags::NUM_LOCK), Some('.'));
+    assert_eq!(translate(0x52, ShiftFlags::LEFT_SHIFT), Some('0'));
+    assert_eq!(translate(0x4F, ShiftFlags::LEFT_SHIFT | ShiftFlags::NUM_LOCK), None);
+    assert_eq!(translate(0x4E, none), Some('+'));
+    assert_eq!(translate(0x4A, ShiftFlags::NUM_LOCK), Some('-'));
+    assert_eq!(translate(0x4A, ShiftFlags::LEFT_SHIFT | ShiftFlags::NUM_LOCK), Some('-'));
+    // Non-printing keys
+    for scancode in [0x01, 0x0E, 0x0F, 0x1C, 0x1D, 0x2A, 0x3B, 0x58, 0xFF] {
+        assert_eq!(translate(scancode, ShiftFlags::NUM_LOCK), None);
+    }
+    println!("Scancodes translated");
+}
@@ -0,0 +1,36 @@
+use rust_dos::*;
+use dos::file::{read_to_string, remove_file};
+use dos::Vec;
+use log::{set_sink, Level, LogSink};
+
+#[allow(dead_code)]
+pub(crate) fn log_test() {
+    let _ = remove_file("TEST.LOG");
+    set_sink(LogSink::append_to_file("TEST.LOG").unwrap());
+    log!(Level::Error, "code {}", 5);
+    log!(Level::Warn, "warning");
+    set_sink(LogSink::Console);
+    log!(Level::Info, "on the console");
+
+    // Appended to
+    set_sink(LogSink::append_to_file("TEST.LOG").unwrap());
+    log!(Level::Info, "{} bytes", 512);
+    log!(Level::Debug, "done");
+    set_sink(LogSink::Console);
+
+    let contents = read_to_string("TEST.LOG").unwrap();
+    let lines: Vec<&str> = contents.split("\r\n").collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[4], "");
+    let expected = ["[ERROR] code 5", "[WARN] warning", "[INFO] 512 bytes", "[DEBUG] done"];
+    for (line, expected) in lines.iter().zip(expected) {
+        // 12:34:56 [LEVEL] message
+        let (time, message) = line.split_at(9);
+        assert_eq!(message, expected);
+        assert_eq!(time.as_bytes()[2], b':');
+        assert_eq!(time.as_bytes()[5], b':');
+        assert_eq!(time.as_bytes()[8], b' ');
+    }
+    remove_file("TEST.LOG").unwrap();
+    println!("Log written to a file");
+}
@@ -0,0 +1,54 @@
+use rust_dos::*;
+use dos::memory::{get_allocation_strategy, get_umb_link, memory_blocks, set_allocation_strategy, AllocationStrategy, DosMemory};
+
+#[allow(dead_code)]
+pub(crate) fn memory_test() {
+    let memory = match DosMemory::allocate(16) {
+        Ok(memory) => memory,
+        Err((error_code, largest_block)) => {
+            println!("Allocation failed: {} (largest block: {} paragraphs)", error_code, largest_block);
+            return;
+        }
+    };
+    assert_eq!(memory.paragraphs(), 16);
+    assert_eq!(memory.len(), 256);
+    assert!(!memory.is_empty());
+    unsafe {
+        memory.as_ptr().write_volatile(0x42);
+        assert_eq!(memory.as_ptr().read_volatile(), 0x42);
+    }
+    drop(memory);
+    println!("Memory block allocated, written and freed");
+}
+
+#[allow(dead_code)]
+pub(crate) fn allocation_strategy_test() {
+    let original_strategy = get_allocation_strategy().unwrap();
+    set_allocation_strategy(AllocationStrategy::BestFit).unwrap();
+    assert_eq!(get_allocation_strategy(), Some(AllocationStrategy::BestFit));
+    set_allocation_strategy(AllocationStrategy::LastFit).unwrap();
+    assert_eq!(get_allocation_strategy(), Some(AllocationStrategy::LastFit));
+    set_allocation_strategy(original_strategy).unwrap();
+    assert_eq!(get_allocation_strategy(), Some(original_strategy));
+    assert_eq!(AllocationStrategy::from_u8(0x41), Some(AllocationStrategy::BestFitHighOnly));
+    assert_eq!(AllocationStrategy::from_u8(0x03), None);
+    println!("Allocation strategy: {:?}, UMB linked: {}", original_strategy, get_umb_link());
+}
+
+#[allow(dead_code)]
+pub(crate) fn memory_blocks_test() {
+    let mut total_paragraphs: u32 = 0;
+    let mut last_block = None;
+    let mut owns_block = false;
+    for block in memory_blocks() {
+        let block = block.unwrap();
+        total_paragraphs += block.size_paragraphs as u32 + 1;
+        owns_block |= block.owner == dos::env::psp_segment();
+        last_block = Some(block);
+    }
+    assert!(last_block.unwrap().is_last);
+    assert!(owns_block);
+    // Between 64 KB and 1 MB
+    assert!(total_paragraphs > 0x1000 && total_paragraphs <= 0x10000);
+    println!("{} paragraphs in memory blocks", total_paragraphs);
+}
@@ -1,3 +1,48 @@
 pub(crate) mod file;
 pub(crate) mod allocator_test;
-pub(crate) mod cooperative_multitasking_test;
\ No newline at end of file
+pub(crate) mod cooperative_multitasking_test;
+pub(crate) mod memory_test;
+pub(crate) mod disk_test;
+pub(crate) mod directory_test;
+pub(crate) mod exit_code_test;
+pub(crate) mod process_test;
+pub(crate) mod interrupt_vector_test;
+pub(crate) mod system_test;
+pub(crate) mod error_code_test;
+pub(crate) mod video_test;
+pub(crate) mod text_screen_test;
+pub(crate) mod keyboard_test;
+pub(crate) mod mouse_test;
+pub(crate) mod timer_test;
+pub(crate) mod panic_test;
+pub(crate) mod env_test;
+pub(crate) mod psp_test;
+pub(crate) mod fcb_test;
+pub(crate) mod country_test;
+pub(crate) mod tsr_test;
+pub(crate) mod ioctl_test;
+pub(crate) mod console_test;
+pub(crate) mod serial_test;
+pub(crate) mod bios_serial_test;
+pub(crate) mod printer_test;
+pub(crate) mod bios_disk_test;
+pub(crate) mod fat_test;
+pub(crate) mod sysvars_test;
+pub(crate) mod text_test;
+pub(crate) mod util_test;
+pub(crate) mod critical_error_test;
+pub(crate) mod datetime_test;
+pub(crate) mod rtc_test;
+pub(crate) mod sound_test;
+pub(crate) mod io_test;
+pub(crate) mod xms_test;
+pub(crate) mod ems_test;
+pub(crate) mod output_test;
+pub(crate) mod log_test;
+
+/// Fills test data with a pattern that doesn't repeat every 256 bytes, so that a misplaced chunk is noticed.
+pub(crate) fn fill_test_pattern(data: &mut [u8]) {
+    for (i, byte) in data.iter_mut().enumerate() {
+        *byte = (i % 251) as u8;
+    }
+}
@@ -0,0 +1,22 @@
+use rust_dos::*;
+use mouse::{get_status, hide, reset, set_position, show, MouseButtons};
+
+#[allow(dead_code)]
+pub(crate) fn mouse_test() {
+    let buttons = match reset() {
+        Some(buttons) => buttons,
+        None => {
+            println!("No mouse driver installed");
+            return;
+        }
+    };
+    println!("Mouse with {} buttons", buttons);
+    let status = get_status();
+    println!("Initial position: {}, {}", status.x, status.y);
+    println!("Left button pressed: {}", status.buttons.contains(MouseButtons::LEFT));
+    set_position(8, 16);
+    let status = get_status();
+    assert_eq!((status.x, status.y), (8, 16));
+    show();
+    hide();
+}
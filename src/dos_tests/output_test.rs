@@ -0,0 +1,56 @@
+use rust_dos::*;
+use core::fmt::Write as _;
+use dos::error_code::ErrorCode;
+use dos::file::{read, remove_file, File, StdioRedirect};
+use dos::output::{FmtWriter, Output};
+
+fn report(output: &mut impl Output, count: usize) -> Result<(), ErrorCode> {
+    write!(output, "{} files, {:>5}|{:<4}|\r\n", count, "right", 'l')?;
+    output.write_bytes(b"raw\r\n")
+}
+
+#[allow(dead_code)]
+pub(crate) fn output_test() {
+    use dos::console::stdout;
+
+    let _ = remove_file("STDOUT.TXT");
+    let _ = remove_file("FILE.TXT");
+    let target = File::create("STDOUT.TXT").unwrap();
+    let redirect = StdioRedirect::new(&target).unwrap();
+    report(&mut stdout(), 42).unwrap();
+    drop(redirect);
+    target.close().unwrap();
+
+    let mut file = File::create("FILE.TXT").unwrap();
+    report(&mut file, 42).unwrap();
+    // Through fmt::Write
+    writeln!(FmtWriter::new(&mut file), "fmt").unwrap();
+    file.close().unwrap();
+
+    let from_stdout = read("STDOUT.TXT").unwrap();
+    let from_file = read("FILE.TXT").unwrap();
+    assert_eq!(from_stdout, b"42 files, right|l   |\r\nraw\r\n");
+    assert_eq!(from_file[..from_stdout.len()], from_stdout[..]);
+    assert_eq!(&from_file[from_stdout.len()..], b"fmt\n");
+    remove_file("STDOUT.TXT").unwrap();
+    remove_file("FILE.TXT").unwrap();
+    println!("Same output to stdout and to a file");
+}
+
+#[allow(dead_code)]
+pub(crate) fn text_screen_output_test() {
+    use bios::text_screen::TextScreen;
+
+    let mut screen = TextScreen::new();
+    screen.set_attribute(0x1F);
+    screen.set_cursor(20, 0);
+    report(&mut screen, 3).unwrap();
+    assert_eq!(screen.cursor(), (22, 0));
+    screen.set_cursor(24, 78);
+    write!(screen, "wrap").unwrap();
+    // Scrolled up by a line
+    assert_eq!(screen.cursor(), (24, 2));
+    screen.set_cursor(99, 99);
+    assert_eq!(screen.cursor(), (24, 79));
+    println!("Written at the cursor of the screen");
+}
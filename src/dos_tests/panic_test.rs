@@ -0,0 +1,6 @@
+/// Use as the program entry with `entry!(panic_test)`,
+/// then check the return code from a batch file with `IF ERRORLEVEL 255`.
+#[allow(dead_code)]
+pub(crate) fn panic_test() {
+    panic!("panic test");
+}
@@ -0,0 +1,12 @@
+use rust_dos::*;
+use bios::printer::{init, status, PrinterStatus};
+
+/// LPT1 must exist.
+#[allow(dead_code)]
+pub(crate) fn printer_test() {
+    init(0);
+    let printer_status = status(0);
+    assert!(!printer_status.contains(PrinterStatus::TIMEOUT));
+    println!("LPT1 status: {:#04x}, busy: {}, out of paper: {}", printer_status.bits(),
+             printer_status.is_busy(), printer_status.out_of_paper());
+}
@@ -0,0 +1,27 @@
+use rust_dos::*;
+use dos::error_code::ErrorCode;
+use dos::panic::PANIC_EXIT_CODE;
+use dos::process::{exec, get_return_code, TerminationReason};
+
+/// EXITCODE.COM is this program built with `entry!(exit_code_test)`.
+#[allow(dead_code)]
+pub(crate) fn exec_test() {
+    exec("EXITCODE.COM", "", None).unwrap();
+    let child_exit = get_return_code();
+    assert_eq!(child_exit.reason, TerminationReason::Normal);
+    assert_eq!(child_exit.code, 3);
+    println!("Child exited with code {}", child_exit.code);
+
+    let long_cmdline = core::str::from_utf8(&[b'A'; 127]).unwrap();
+    assert_eq!(exec("EXITCODE.COM", long_cmdline, None), Err(ErrorCode::InvalidData));
+}
+
+/// PANIC.COM is this program built with `entry!(panic_test)`.
+#[allow(dead_code)]
+pub(crate) fn exec_panic_test() {
+    exec("PANIC.COM", "", None).unwrap();
+    let child_exit = get_return_code();
+    assert_eq!(child_exit.reason, TerminationReason::Normal);
+    assert_eq!(child_exit.code, PANIC_EXIT_CODE);
+    println!("Child panicked with code {}", child_exit.code);
+}
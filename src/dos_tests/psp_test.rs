@@ -0,0 +1,32 @@
+use rust_dos::*;
+use dos::env::psp_segment;
+use psp::Psp;
+
+#[allow(dead_code)]
+pub(crate) fn psp_test() {
+    let psp = Psp::current();
+    assert_eq!(psp.segment(), psp_segment());
+    assert_ne!(psp.environment_segment(), 0);
+    assert_ne!(psp.parent_psp(), 0);
+    assert!(!psp.terminate_address().is_null());
+    println!("PSP: {:#06x}, environment: {:#06x}, parent: {:#06x}", psp.segment(), psp.environment_segment(), psp.parent_psp());
+}
+
+#[allow(dead_code)]
+pub(crate) fn psp_guard_test() {
+    use psp::{get_current_psp, PspGuard};
+
+    let segment = get_current_psp();
+    {
+        let _guard = PspGuard::new(segment);
+        assert_eq!(get_current_psp(), segment);
+        {
+            // The parent, usually COMMAND.COM: only AH=51h is called while it is current
+            let _nested = PspGuard::new(Psp::current().parent_psp());
+            assert_eq!(get_current_psp(), Psp::new(segment).parent_psp());
+        }
+        assert_eq!(get_current_psp(), segment);
+    }
+    assert_eq!(get_current_psp(), segment);
+    println!("PSP {:#06x} restored", segment);
+}
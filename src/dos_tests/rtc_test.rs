@@ -0,0 +1,13 @@
+use rust_dos::*;
+use bios::rtc::{get_rtc_date, get_rtc_time};
+
+#[allow(dead_code)]
+pub(crate) fn rtc_test() {
+    let date = get_rtc_date().unwrap();
+    let time = get_rtc_time().unwrap();
+    assert!(date.year >= 1980);
+    assert!((1..=12).contains(&date.month) && (1..=31).contains(&date.day));
+    assert!(time.hour < 24 && time.minute < 60 && time.second < 60);
+    println!("RTC: {}-{:02}-{:02} {:02}:{:02}:{:02}", date.year, date.month, date.day,
+             time.hour, time.minute, time.second);
+}
@@ -0,0 +1,16 @@
+use rust_dos::*;
+use dos::serial::Serial;
+
+/// Needs a loopback plug or null-modem on AUX, the test is skipped otherwise.
+#[allow(dead_code)]
+pub(crate) fn serial_loopback_test() {
+    let serial = Serial::new();
+    serial.write_byte(b'R');
+    match serial.read_with_timeout(18) {
+        Some(byte) => {
+            assert_eq!(byte, b'R');
+            println!("Serial loopback OK");
+        }
+        None => println!("No loopback on AUX, skipped"),
+    }
+}
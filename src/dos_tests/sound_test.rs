@@ -0,0 +1,16 @@
+use rust_dos::*;
+use bios::timer::sleep;
+use sound::{beep, silence, tone};
+
+/// Audible on real hardware with a PC speaker, and in DOSBox.
+#[allow(dead_code)]
+pub(crate) fn sound_test() {
+    beep(1000, 5);
+    sleep(2);
+    for frequency in [262, 330, 392, 523] {
+        tone(frequency);
+        sleep(3);
+    }
+    silence();
+    println!("Tones played");
+}
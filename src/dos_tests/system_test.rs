@@ -0,0 +1,82 @@
+use rust_dos::*;
+use core::sync::atomic::{AtomicBool, Ordering};
+use dos::system::{get_ctrl_break, get_switch_char, get_verify, in_dos_flag, install_ctrl_c_handler, is_dos_busy,
+                  set_ctrl_break, set_verify, set_wait_for_external_event, uninstall_ctrl_c_handler};
+
+#[allow(dead_code)]
+pub(crate) fn ctrl_break_test() {
+    let original_state = get_ctrl_break();
+    set_ctrl_break(!original_state);
+    assert_eq!(get_ctrl_break(), !original_state);
+    set_ctrl_break(original_state);
+    assert_eq!(get_ctrl_break(), original_state);
+    println!("Ctrl-Break checking toggled");
+}
+
+#[allow(dead_code)]
+pub(crate) fn in_dos_flag_test() {
+    let flag = in_dos_flag();
+    assert!(!flag.is_null());
+    // We aren't in a DOS call right now
+    assert_eq!(unsafe { flag.read_volatile() }, 0);
+    assert!(!is_dos_busy());
+    println!("InDOS flag is clear");
+}
+
+#[allow(dead_code)]
+pub(crate) fn switch_char_test() {
+    let switch_char = get_switch_char();
+    // Default on DOSBox
+    assert_eq!(switch_char, b'/');
+    println!("Switch character is {}", switch_char as char);
+}
+
+#[allow(dead_code)]
+pub(crate) fn boot_drive_test() {
+    use dos::system::{dos_version_at_least, get_boot_drive};
+
+    match get_boot_drive() {
+        Some(drive) => println!("Booted from {}", drive),
+        None => {
+            assert!(!dos_version_at_least(4, 0));
+            println!("Boot drive unknown before DOS 4.0");
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub(crate) fn verify_test() {
+    let original_state = get_verify();
+    set_verify(true);
+    assert!(get_verify());
+    set_verify(original_state);
+    assert_eq!(get_verify(), original_state);
+    println!("Verify flag toggled");
+}
+
+#[allow(dead_code)]
+pub(crate) fn wait_for_external_event_test() {
+    set_wait_for_external_event(true);
+    set_wait_for_external_event(false);
+    println!("Wait for external event flag set and cleared");
+}
+
+static CTRL_C_PRESSED: AtomicBool = AtomicBool::new(false);
+
+// No DOS call here, see install_ctrl_c_handler
+fn on_ctrl_c() {
+    CTRL_C_PRESSED.store(true, Ordering::Relaxed);
+}
+
+/// Needs Ctrl-C pressed, or Ctrl-Break: Ctrl+Pause in DOSBox.
+#[allow(dead_code)]
+pub(crate) fn ctrl_c_handler_test() {
+    install_ctrl_c_handler(on_ctrl_c);
+    println!("Press Ctrl-C");
+    // Console output checks for Ctrl-C
+    while !CTRL_C_PRESSED.load(Ordering::Relaxed) {
+        print!(".");
+    }
+    uninstall_ctrl_c_handler();
+    println!("\r\nCtrl-C handled, still running");
+}
@@ -0,0 +1,17 @@
+use rust_dos::*;
+use dos::system::dos_version_at_least;
+use dos::sysvars::{first_dpb, get_list_of_lists, last_drive, mcb_chain_start};
+
+#[allow(dead_code)]
+pub(crate) fn list_of_lists_test() {
+    if !dos_version_at_least(3, 10) {
+        println!("DOS 3.1+ needed, skipped");
+        return;
+    }
+    assert!(!get_list_of_lists().is_null());
+    assert!(!first_dpb().is_null());
+    assert_ne!(mcb_chain_start(), 0);
+    // At least A: to C:
+    assert!(last_drive().unwrap() >= 3);
+    println!("First MCB at {:#06x}, LASTDRIVE={}", mcb_chain_start(), last_drive().unwrap());
+}
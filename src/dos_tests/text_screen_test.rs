@@ -0,0 +1,46 @@
+use rust_dos::*;
+use bios::text_screen::TextScreen;
+use bios::video::{set_mode, VideoMode};
+
+#[allow(dead_code)]
+pub(crate) fn text_screen_test() {
+    set_mode(VideoMode::Text80x25);
+    let mut screen = TextScreen::new();
+    screen.clear(0x07);
+    for row in 0..TextScreen::HEIGHT {
+        for col in 0..TextScreen::WIDTH {
+            let color = ((row + col) % 16) as u8;
+            screen.put(row, col, 0xB1, color << 4 | (15 - color));
+        }
+    }
+    // out of range, ignored
+    screen.put(TextScreen::HEIGHT, 0, b'X', 0x4F);
+    screen.put(0, TextScreen::WIDTH, b'X', 0x4F);
+    screen.write_str_at(12, 30, " TextScreen test ", 0x1F);
+}
+
+#[allow(dead_code)]
+pub(crate) fn screen_flush_test() {
+    use bios::text_screen::Screen;
+
+    set_mode(VideoMode::Text80x25);
+    let mut screen = Screen::new();
+    screen.clear(0x07);
+    screen.flush();
+    assert_eq!(screen.flush(), 0);
+
+    screen.write_str(12, 30, "Screen", 0x1F);
+    screen.set(0, 0, TextScreen::cell(b'*', 0x4F));
+    assert_eq!(screen.flush(), 7);
+    // Same contents, nothing to write
+    screen.write_str(12, 30, "Screen", 0x1F);
+    assert_eq!(screen.flush(), 0);
+    screen.set(12, 30, TextScreen::cell(b's', 0x1F));
+    assert_eq!(screen.flush(), 1);
+    screen.set(TextScreen::HEIGHT, 0, TextScreen::cell(b'X', 0x4F));
+    assert_eq!(screen.flush(), 0);
+    assert_eq!(screen.get(12, 30), Some(TextScreen::cell(b's', 0x1F)));
+
+    screen.invalidate();
+    assert_eq!(screen.flush(), TextScreen::WIDTH * TextScreen::HEIGHT);
+}
@@ -0,0 +1,19 @@
+use rust_dos::*;
+use bios::text::{Attribute, Color};
+use bios::text_screen::TextScreen;
+use bios::video::{set_mode, VideoMode};
+
+#[allow(dead_code)]
+pub(crate) fn attribute_test() {
+    assert_eq!(Attribute::new(Color::White, Color::Blue).into_byte(), 0x1F);
+    assert_eq!(Attribute::new(Color::LightGray, Color::Black).into_byte(), 0x07);
+    assert_eq!(Attribute::default().into_byte(), 0x07);
+    assert_eq!(Attribute::new(Color::Yellow, Color::Red).blinking().into_byte(), 0xCE);
+    assert_eq!(u8::from(Attribute::new(Color::Black, Color::LightGray)), 0x70);
+
+    set_mode(VideoMode::Text80x25);
+    let mut screen = TextScreen::new();
+    screen.clear(Attribute::default());
+    screen.write_str_at(12, 30, " Attribute test ", Attribute::new(Color::White, Color::Blue));
+    screen.write_str_at(13, 30, " Blinking ", Attribute::new(Color::Yellow, Color::Red).blinking());
+}
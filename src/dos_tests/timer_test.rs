@@ -0,0 +1,39 @@
+use rust_dos::*;
+use bios::timer::{midnight_rollover, read_tick_count, ticks_to_millis};
+
+#[allow(dead_code)]
+pub(crate) fn timer_test() {
+    assert_eq!(ticks_to_millis(0), 0);
+    assert_eq!(ticks_to_millis(1000), 54925);
+    midnight_rollover();
+    let start = read_tick_count();
+    let mut end = start;
+    while end == start {
+        end = read_tick_count();
+    }
+    assert!(end > start || midnight_rollover());
+    println!("Ticks: {} -> {} ({} ms)", start, end, ticks_to_millis(end.wrapping_sub(start)));
+}
+
+#[allow(dead_code)]
+pub(crate) fn sleep_test() {
+    use bios::timer::{millis_to_ticks, sleep, sleep_ms, TICKS_PER_DAY};
+
+    assert_eq!(millis_to_ticks(0), 0);
+    assert_eq!(millis_to_ticks(1), 1);
+    assert_eq!(millis_to_ticks(54), 1);
+    assert_eq!(millis_to_ticks(55), 2);
+    assert_eq!(millis_to_ticks(500), 10);
+
+    let start = read_tick_count();
+    sleep_ms(500);
+    let end = read_tick_count();
+    let elapsed = if end >= start { end - start } else { end + TICKS_PER_DAY - start };
+    assert!((10..=12).contains(&elapsed), "{} ticks elapsed", elapsed);
+
+    let start = read_tick_count();
+    sleep(3);
+    let end = read_tick_count();
+    assert!(end.wrapping_sub(start) >= 3);
+    println!("Slept {} ms", ticks_to_millis(elapsed));
+}
@@ -0,0 +1,29 @@
+use rust_dos::*;
+use core::ptr::addr_of;
+use dos::far_ptr::FarPtr;
+use dos::interrupt_vector::set_interrupt_vector;
+use dos::process::{resident_paragraphs, stay_resident};
+
+extern "C" {
+    // End of the program image, from the linker script
+    static _heap: u8;
+}
+
+// Intra-application communication area of the BIOS, readable by other programs
+const TICKS: FarPtr = FarPtr::new(0x0040, 0x00F0);
+
+fn on_tick() {
+    let ticks = TICKS.as_mut_ptr::<u32>();
+    unsafe { ticks.write_volatile(ticks.read_volatile().wrapping_add(1)) }
+}
+
+/// Example TSR: counts the timer ticks into 0040:00F0, then stays resident.
+/// Run `DEBUG` and `D 40:F0` to watch the counter.
+#[allow(dead_code)]
+pub(crate) fn tsr_test() {
+    unsafe { TICKS.as_mut_ptr::<u32>().write_volatile(0) }
+    set_interrupt_vector(0x1C, interrupt_handler!(on_tick));
+    let paragraphs = resident_paragraphs(addr_of!(_heap));
+    println!("Staying resident with {} paragraphs", paragraphs);
+    stay_resident(0, paragraphs);
+}
@@ -0,0 +1,34 @@
+use rust_dos::*;
+use core::fmt::Write;
+use dos::error_code::ErrorCode;
+use dos::file::File;
+use util::{ArrayString, CapacityError};
+
+#[allow(dead_code)]
+pub(crate) fn array_string_test() {
+    let mut path = ArrayString::<12>::new();
+    assert_eq!(path.capacity(), 11);
+    path.push_str("C:\\").unwrap();
+    path.push('A').unwrap();
+    write!(path, "{}", 42).unwrap();
+    assert_eq!(path.as_bytes(), b"C:\\A42");
+    let terminated = unsafe { core::slice::from_raw_parts(path.as_c_str(), path.len() + 1) };
+    assert_eq!(terminated, b"C:\\A42\0");
+
+    // Overflowing appends nothing
+    assert_eq!(path.push_str("TOOLONG"), Err(CapacityError));
+    assert_eq!(path.as_bytes(), b"C:\\A42");
+    path.push_str("12345").unwrap();
+    assert_eq!(path.len(), 11);
+    assert_eq!(path.push('X'), Err(CapacityError));
+    let terminated = unsafe { core::slice::from_raw_parts(path.as_c_str(), path.len() + 1) };
+    assert_eq!(terminated, b"C:\\A4212345\0");
+
+    path.clear();
+    assert!(path.is_empty());
+    assert_eq!(unsafe { *path.as_c_str() }, 0);
+
+    let long_path = "C:\\A\\B\\C\\D\\E\\F\\G\\H\\I\\J\\K\\L\\M\\N\\O\\P\\Q\\R\\S\\T\\U\\V\\W\\X\\Y\\Z\\LONGDIRECTORY\\ABCDEF.TXT";
+    assert!(matches!(File::open(long_path), Err(ErrorCode::PathNotFound)));
+    println!("ArrayString OK");
+}
@@ -0,0 +1,34 @@
+use rust_dos::*;
+use bios::video::{get_cursor_position, set_cursor_position, set_mode, write_char_at, VideoMode};
+
+#[allow(dead_code)]
+pub(crate) fn video_test() {
+    set_mode(VideoMode::Text80x25);
+    set_cursor_position(0, 10, 20);
+    assert_eq!(get_cursor_position(0), (10, 20));
+    write_char_at(b'*', 0x1E, 5);
+    set_cursor_position(0, 12, 0);
+    println!("Cursor moved to row 10, column 20");
+}
+
+/// Needs a key press to return to text mode.
+#[allow(dead_code)]
+pub(crate) fn mode_13h_test() {
+    use bios::keyboard::read_key;
+    use bios::video::{clear_screen, put_pixel, set_palette_entry, MODE_13H_HEIGHT, MODE_13H_WIDTH};
+
+    set_mode(VideoMode::Vga320x200);
+    clear_screen(1);
+    // Bright orange
+    set_palette_entry(200, 63, 32, 0);
+    for i in 0..MODE_13H_HEIGHT {
+        put_pixel(i, i, 200);
+        put_pixel(MODE_13H_WIDTH - 1 - i, i, 15);
+    }
+    // out of range, ignored
+    put_pixel(MODE_13H_WIDTH, 0, 4);
+    put_pixel(0, MODE_13H_HEIGHT, 4);
+    read_key();
+    set_mode(VideoMode::Text80x25);
+    println!("Mode 13h diagonals drawn");
+}
@@ -0,0 +1,22 @@
+use rust_dos::*;
+use xms::{allocate, is_installed, query_free};
+
+#[allow(dead_code)]
+pub(crate) fn xms_test() {
+    if !is_installed() {
+        println!("No XMS driver");
+        return;
+    }
+    let (largest, total) = query_free().unwrap();
+    println!("Free extended memory: {} KB, largest block {} KB", total, largest);
+    let block = allocate(64).unwrap();
+    assert_eq!(block.kb(), 64);
+    let data = *b"Extended memory!";
+    block.write(65520, &data).unwrap();
+    let mut buffer = [0u8; 16];
+    block.read(65520, &mut buffer).unwrap();
+    assert_eq!(buffer, data);
+    assert_eq!(block.write(0, b"odd"), Err(xms::XmsError::InvalidLength));
+    block.free().unwrap();
+    println!("Allocated and freed a 64 KB block");
+}
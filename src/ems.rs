@@ -0,0 +1,176 @@
+//! Expanded memory, through an EMS driver such as EMM386 (INT 67h).
+//!
+//! Expanded memory is allocated in pages of 16 KB, that are accessed by mapping them in one of the 4 physical
+//! pages of the page frame, below 1 MB. Like extended memory, it isn't freed by DOS when the program terminates:
+//! [EmsHandle]s are freed when dropped.
+
+use core::arch::asm;
+use core::fmt;
+use crate::dos::far_ptr::FarPtr;
+use crate::dos::file::File;
+use crate::dos::ioctl::get_device_info;
+
+/// Size of a page, logical or physical.
+pub const PAGE_SIZE: usize = 16384;
+/// Physical pages in the page frame.
+pub const PHYSICAL_PAGES: u8 = 4;
+
+/// Error code returned by the driver in AH.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmsError {
+    /// No EMS driver is loaded
+    NotInstalled,
+    InternalError,
+    HardwareMalfunction,
+    InvalidHandle,
+    UndefinedFunction,
+    /// All the handles are used
+    OutOfHandles,
+    /// More pages than the system has
+    NotEnoughPages,
+    /// More pages than are free
+    NotEnoughFreePages,
+    ZeroPages,
+    InvalidLogicalPage,
+    InvalidPhysicalPage,
+    Other(u8),
+}
+
+impl From<u8> for EmsError {
+    fn from(code: u8) -> Self {
+        match code {
+            0x80 => EmsError::InternalError,
+            0x81 => EmsError::HardwareMalfunction,
+            0x83 => EmsError::InvalidHandle,
+            0x84 => EmsError::UndefinedFunction,
+            0x85 => EmsError::OutOfHandles,
+            0x87 => EmsError::NotEnoughPages,
+            0x88 => EmsError::NotEnoughFreePages,
+            0x89 => EmsError::ZeroPages,
+            0x8A => EmsError::InvalidLogicalPage,
+            0x8B => EmsError::InvalidPhysicalPage,
+            code => EmsError::Other(code),
+        }
+    }
+}
+
+impl fmt::Display for EmsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EmsError::NotInstalled => write!(f, "No EMS driver"),
+            EmsError::NotEnoughFreePages => write!(f, "Out of expanded memory"),
+            EmsError::OutOfHandles => write!(f, "Out of EMS handles"),
+            EmsError::Other(code) => write!(f, "EMS error {:02X}h", code),
+            error => write!(f, "{:?}", error),
+        }
+    }
+}
+
+/// Returns true if an EMS driver is loaded, that is if the `EMMXXXX0` device can be opened.
+/// INT 67h must not be called otherwise, as its vector may be anything.
+pub fn is_installed() -> bool {
+    match File::open("EMMXXXX0") {
+        // A file with the same name in the current directory would also open
+        Ok(file) => get_device_info(file.handle()).map(|info| info.is_device()).unwrap_or(false),
+        Err(_) => false,
+    }
+}
+
+/// Calls a driver function with AX, BX and DX, and returns BX and DX, or the error code in AH.
+fn ems_function(ax: u16, bx: u16, dx: u16) -> Result<(u16, u16), EmsError> {
+    let status: u16;
+    let bx_out: u16;
+    let dx_out: u16;
+    unsafe {
+        asm!("int 0x67",
+             inout("ax") ax => status,
+             inout("bx") bx => bx_out,
+             inout("dx") dx => dx_out);
+    }
+    match (status >> 8) as u8 {
+        0 => Ok((bx_out, dx_out)),
+        code => Err(EmsError::from(code)),
+    }
+}
+
+/// Checks that the driver is loaded and the hardware works (INT 67h AH=40h).
+pub fn status() -> Result<(), EmsError> {
+    if !is_installed() {
+        return Err(EmsError::NotInstalled);
+    }
+    ems_function(0x4000, 0, 0)?;
+    Ok(())
+}
+
+/// Gets the segment of the page frame (INT 67h AH=41h). Physical page `n` starts at offset `n * PAGE_SIZE`.
+pub fn page_frame_segment() -> Option<u16> {
+    if !is_installed() {
+        return None;
+    }
+    ems_function(0x4100, 0, 0).ok().map(|(segment, _)| segment)
+}
+
+/// Gets the (total, free) number of pages (INT 67h AH=42h), or (0, 0) without a driver.
+pub fn total_and_free_pages() -> (u16, u16) {
+    if !is_installed() {
+        return (0, 0);
+    }
+    match ems_function(0x4200, 0, 0) {
+        Ok((free, total)) => (total, free),
+        Err(_) => (0, 0),
+    }
+}
+
+/// Allocates logical pages of expanded memory (INT 67h AH=43h).
+pub fn allocate(pages: u16) -> Result<EmsHandle, EmsError> {
+    if !is_installed() {
+        return Err(EmsError::NotInstalled);
+    }
+    let (_, handle) = ems_function(0x4300, pages, 0)?;
+    Ok(EmsHandle { handle, pages })
+}
+
+/// Logical pages of expanded memory, freed when dropped.
+#[derive(Debug)]
+pub struct EmsHandle {
+    handle: u16,
+    pages: u16,
+}
+
+impl EmsHandle {
+    pub fn handle(&self) -> u16 {
+        self.handle
+    }
+
+    /// Number of logical pages.
+    pub fn pages(&self) -> u16 {
+        self.pages
+    }
+
+    /// Maps the logical page `logical` of the handle in the physical page `physical` (0 to 3) of the page frame
+    /// (INT 67h AH=44h), and returns a pointer to it.
+    /// The pointer is valid until another page is mapped at the same physical page.
+    pub fn map_page(&self, logical: u16, physical: u8) -> Result<FarPtr, EmsError> {
+        ems_function(0x4400 | physical as u16, logical, self.handle)?;
+        let (segment, _) = ems_function(0x4100, 0, 0)?;
+        Ok(FarPtr::new(segment + physical as u16 * (PAGE_SIZE / 16) as u16, 0))
+    }
+
+    /// Frees the pages (INT 67h AH=45h).
+    pub fn free(self) -> Result<(), EmsError> {
+        let result = self.free_with_ref();
+        core::mem::forget(self);
+        result
+    }
+
+    fn free_with_ref(&self) -> Result<(), EmsError> {
+        ems_function(0x4500, 0, self.handle)?;
+        Ok(())
+    }
+}
+
+impl Drop for EmsHandle {
+    fn drop(&mut self) {
+        let _ = self.free_with_ref();
+    }
+}
@@ -0,0 +1,333 @@
+//! Read-only FAT12/FAT16 file system, reading the sectors of a drive directly instead of asking DOS.
+//! Files are read even if another program has them open with sharing denied.
+//!
+//! The drive must be a FAT drive, not a network or CD-ROM one. DOS may have newer data in its buffers:
+//! commit or close files before reading them here.
+
+use core::cell::RefCell;
+use core::fmt;
+use crate::bios::disk::SECTOR_SIZE;
+use crate::dos::datetime::{Date, Time};
+use crate::dos::disk::{get_allocation_info_for_specified_drive, read_absolute_sectors, DriveLetter};
+use crate::dos::error_code::ErrorCode;
+use crate::dos::file::FileAttributes;
+use crate::dos::{String, Vec, vec};
+
+/// FAT12 is used below 4085 clusters, FAT16 from there.
+const FAT12_MAX_CLUSTERS: u16 = 4084;
+const DIRECTORY_ENTRY_SIZE: usize = 32;
+/// Sectors read by each INT 25h call, keeping the buffers small: the heap is 64 KB, as is the most a single call
+/// can transfer, and a cluster or a FAT can be that large.
+const SECTORS_PER_READ: u16 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FatError {
+    /// Reading a sector failed
+    Dos(ErrorCode),
+    /// The boot sector doesn't describe a FAT file system
+    NotFat,
+    /// A component of the path doesn't exist
+    NotFound,
+    /// The last component of the path is a directory, or another one is a file
+    NotAFile,
+    /// A cluster chain is broken or loops
+    Corrupted,
+}
+
+impl From<ErrorCode> for FatError {
+    fn from(error: ErrorCode) -> Self {
+        FatError::Dos(error)
+    }
+}
+
+impl fmt::Display for FatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FatError::Dos(error) => write!(f, "{}", error),
+            FatError::NotFat => write!(f, "Not a FAT file system"),
+            FatError::NotFound => write!(f, "File not found"),
+            FatError::NotAFile => write!(f, "Not a file"),
+            FatError::Corrupted => write!(f, "Corrupted cluster chain"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FatType {
+    Fat12,
+    Fat16,
+}
+
+/// An entry of a directory.
+#[derive(Debug, Clone)]
+pub struct DirEntry {
+    /// Name and extension, padded with spaces
+    pub raw_name: [u8; 11],
+    pub attributes: FileAttributes,
+    /// Last modification
+    pub date: Date,
+    pub time: Time,
+    pub first_cluster: u16,
+    pub size: u32,
+}
+
+impl DirEntry {
+    fn parse(entry: &[u8]) -> Self {
+        let mut raw_name = [0; 11];
+        raw_name.copy_from_slice(&entry[..11]);
+        DirEntry {
+            raw_name,
+            attributes: FileAttributes::from_bits(entry[11]),
+            date: Date::from_dos_word(u16::from_le_bytes([entry[24], entry[25]])),
+            time: Time::from_dos_word(u16::from_le_bytes([entry[22], entry[23]])),
+            first_cluster: u16::from_le_bytes([entry[26], entry[27]]),
+            size: u32::from_le_bytes([entry[28], entry[29], entry[30], entry[31]]),
+        }
+    }
+
+    /// Name as displayed by DOS, such as `README.TXT`.
+    pub fn name(&self) -> String {
+        let mut name: String = self.raw_name[..8].iter().take_while(|&&c| c != b' ').map(|&c| c as char).collect();
+        let extension = self.raw_name[8..].iter().take_while(|&&c| c != b' ');
+        if self.raw_name[8] != b' ' {
+            name.push('.');
+            name.extend(extension.map(|&c| c as char));
+        }
+        name
+    }
+
+    pub fn is_directory(&self) -> bool {
+        self.attributes.contains(FileAttributes::DIRECTORY)
+    }
+}
+
+/// The FAT sector last read.
+struct FatCache {
+    sector: Option<u16>,
+    data: [u8; SECTOR_SIZE],
+}
+
+/// A FAT drive. The FAT is read a sector at a time, when following a cluster chain needs it.
+pub struct FatVolume {
+    drive: DriveLetter,
+    fat_type: FatType,
+    sectors_per_cluster: u8,
+    root_directory_start: u32,
+    root_directory_entries: u16,
+    data_start: u32,
+    cluster_count: u16,
+    fat_start: u32,
+    sectors_per_fat: u16,
+    fat_cache: RefCell<FatCache>,
+}
+
+impl FatVolume {
+    /// Reads the boot sector of a drive.
+    pub fn open(drive: DriveLetter) -> Result<Self, FatError> {
+        let mut boot_sector = [0; SECTOR_SIZE];
+        read_absolute_sectors(drive, 0, 1, &mut boot_sector)?;
+        let read_u16 = |offset: usize| u16::from_le_bytes([boot_sector[offset], boot_sector[offset + 1]]);
+        let bytes_per_sector = read_u16(0x0B);
+        let sectors_per_cluster = boot_sector[0x0D];
+        let reserved_sectors = read_u16(0x0E);
+        let fat_count = boot_sector[0x10];
+        let root_directory_entries = read_u16(0x11);
+        let sectors_per_fat = read_u16(0x16);
+        if boot_sector[510..] != [0x55, 0xAA] || bytes_per_sector as usize != SECTOR_SIZE || sectors_per_cluster == 0
+            || fat_count == 0 || sectors_per_fat == 0 {
+            return Err(FatError::NotFat);
+        }
+        let allocation_info = get_allocation_info_for_specified_drive(drive).ok_or(FatError::Dos(ErrorCode::InvalidDrive))?;
+        let cluster_count = allocation_info.number_of_clusters;
+        let fat_type = if cluster_count <= FAT12_MAX_CLUSTERS { FatType::Fat12 } else { FatType::Fat16 };
+
+        let root_directory_start = reserved_sectors as u32 + fat_count as u32 * sectors_per_fat as u32;
+        let root_directory_sectors = (root_directory_entries as u32 * DIRECTORY_ENTRY_SIZE as u32)
+            .div_ceil(SECTOR_SIZE as u32);
+        Ok(FatVolume {
+            drive,
+            fat_type,
+            sectors_per_cluster,
+            root_directory_start,
+            root_directory_entries,
+            data_start: root_directory_start + root_directory_sectors,
+            cluster_count,
+            fat_start: reserved_sectors as u32,
+            sectors_per_fat,
+            fat_cache: RefCell::new(FatCache { sector: None, data: [0; SECTOR_SIZE] }),
+        })
+    }
+
+    pub fn fat_type(&self) -> FatType {
+        self.fat_type
+    }
+
+    /// Reads `count` sectors from `start`, by chunks of [SECTORS_PER_READ] given to `f` until it returns false.
+    fn read_sectors(&self, start: u32, count: u16, mut f: impl FnMut(&[u8]) -> bool) -> Result<(), FatError> {
+        let mut chunk = vec![0; count.min(SECTORS_PER_READ) as usize * SECTOR_SIZE];
+        let mut sectors_read = 0;
+        while sectors_read < count {
+            let chunk_sectors = (count - sectors_read).min(SECTORS_PER_READ);
+            let chunk = &mut chunk[..chunk_sectors as usize * SECTOR_SIZE];
+            read_absolute_sectors(self.drive, start + sectors_read as u32, chunk_sectors, chunk)?;
+            if !f(chunk) {
+                break;
+            }
+            sectors_read += chunk_sectors;
+        }
+        Ok(())
+    }
+
+    /// Byte of the first FAT, reading its sector if it isn't the one in the cache.
+    fn fat_byte(&self, offset: usize) -> Result<u8, FatError> {
+        let sector = offset / SECTOR_SIZE;
+        if sector >= self.sectors_per_fat as usize {
+            return Err(FatError::Corrupted);
+        }
+        let mut cache = self.fat_cache.borrow_mut();
+        if cache.sector != Some(sector as u16) {
+            // Left invalid if the read fails
+            cache.sector = None;
+            read_absolute_sectors(self.drive, self.fat_start + sector as u32, 1, &mut cache.data)?;
+            cache.sector = Some(sector as u16);
+        }
+        Ok(cache.data[offset % SECTOR_SIZE])
+    }
+
+    /// Next cluster of a chain, or None at its end.
+    fn next_cluster(&self, cluster: u16) -> Result<Option<u16>, FatError> {
+        // A FAT12 entry can straddle two sectors
+        let read_u16 = |offset: usize| Ok::<_, FatError>(u16::from_le_bytes([self.fat_byte(offset)?, self.fat_byte(offset + 1)?]));
+        let (next, end_of_chain) = match self.fat_type {
+            FatType::Fat12 => {
+                // Entries are 12 bits, packed in pairs in 3 bytes
+                let pair = read_u16(cluster as usize + cluster as usize / 2)?;
+                (if cluster & 1 == 0 { pair & 0x0FFF } else { pair >> 4 }, 0x0FF8)
+            }
+            FatType::Fat16 => (read_u16(cluster as usize * 2)?, 0xFFF8),
+        };
+        if next >= end_of_chain {
+            return Ok(None);
+        }
+        self.check_cluster(next)?;
+        Ok(Some(next))
+    }
+
+    fn check_cluster(&self, cluster: u16) -> Result<(), FatError> {
+        // Clusters are numbered from 2
+        if cluster < 2 || cluster >= self.cluster_count + 2 {
+            return Err(FatError::Corrupted);
+        }
+        Ok(())
+    }
+
+    /// Reads every cluster of a chain, stopping after `max_length` bytes.
+    fn read_chain(&self, first_cluster: u16, max_length: usize) -> Result<Vec<u8>, FatError> {
+        let mut data = Vec::new();
+        let mut clusters_read = 0;
+        // Empty files have no cluster
+        let mut cluster = if first_cluster == 0 { None } else { Some(first_cluster) };
+        if cluster.is_some() {
+            self.check_cluster(first_cluster)?;
+        }
+        while let Some(current) = cluster {
+            if data.len() >= max_length {
+                break;
+            }
+            clusters_read += 1;
+            if clusters_read > self.cluster_count {
+                return Err(FatError::Corrupted);
+            }
+            let sector = self.data_start + (current as u32 - 2) * self.sectors_per_cluster as u32;
+            self.read_sectors(sector, self.sectors_per_cluster as u16, |chunk| {
+                let length = chunk.len().min(max_length - data.len());
+                data.extend_from_slice(&chunk[..length]);
+                data.len() < max_length
+            })?;
+            cluster = self.next_cluster(current)?;
+        }
+        Ok(data)
+    }
+
+    /// Lists the root directory, skipping deleted entries, volume labels and long file names.
+    pub fn root_directory(&self) -> Result<Vec<DirEntry>, FatError> {
+        let mut remaining = self.root_directory_entries as usize * DIRECTORY_ENTRY_SIZE;
+        let sectors = remaining.div_ceil(SECTOR_SIZE);
+        let mut entries = Vec::new();
+        self.read_sectors(self.root_directory_start, sectors as u16, |chunk| {
+            let length = chunk.len().min(remaining);
+            remaining -= length;
+            parse_directory(&chunk[..length], &mut entries)
+        })?;
+        Ok(entries)
+    }
+
+    /// Lists a subdirectory.
+    pub fn directory(&self, entry: &DirEntry) -> Result<Vec<DirEntry>, FatError> {
+        if !entry.is_directory() {
+            return Err(FatError::NotAFile);
+        }
+        if entry.first_cluster == 0 {
+            // `..` of a directory in the root
+            return self.root_directory();
+        }
+        let mut entries = Vec::new();
+        parse_directory(&self.read_chain(entry.first_cluster, usize::MAX)?, &mut entries);
+        Ok(entries)
+    }
+
+    /// Reads a whole file, from a path such as `C:\DOS\README.TXT` or `DOS\README.TXT`, from the root.
+    pub fn read_file(&self, path: &str) -> Result<Vec<u8>, FatError> {
+        let path = match path.as_bytes() {
+            [_, b':', ..] => &path[2..],
+            _ => path,
+        };
+        let mut entries = self.root_directory()?;
+        let mut components = path.split('\\').filter(|component| !component.is_empty()).peekable();
+        while let Some(component) = components.next() {
+            let raw_name = to_raw_name(component);
+            let entry = entries.into_iter().find(|entry| entry.raw_name == raw_name).ok_or(FatError::NotFound)?;
+            if components.peek().is_none() {
+                if entry.is_directory() {
+                    return Err(FatError::NotAFile);
+                }
+                return self.read_chain(entry.first_cluster, entry.size as usize);
+            }
+            entries = self.directory(&entry)?;
+        }
+        Err(FatError::NotAFile)
+    }
+}
+
+/// Appends the entries of directory data to `entries`. Returns false once the end of the directory is reached.
+fn parse_directory(data: &[u8], entries: &mut Vec<DirEntry>) -> bool {
+    for entry in data.chunks_exact(DIRECTORY_ENTRY_SIZE) {
+        if entry[0] == 0x00 {
+            return false;
+        }
+        if entry[0] != 0xE5 && !FileAttributes::from_bits(entry[11]).contains(FileAttributes::VOLUME_LABEL) {
+            entries.push(DirEntry::parse(entry));
+        }
+    }
+    true
+}
+
+/// Name as stored in a directory entry: uppercase, and padded with spaces to 8 + 3 characters.
+fn to_raw_name(name: &str) -> [u8; 11] {
+    let mut raw_name = [b' '; 11];
+    if name == "." || name == ".." {
+        raw_name[..name.len()].copy_from_slice(name.as_bytes());
+        return raw_name;
+    }
+    let (base, extension) = match name.rfind('.') {
+        Some(index) if index > 0 => (&name[..index], &name[index + 1..]),
+        _ => (name, ""),
+    };
+    for (i, c) in base.bytes().take(8).enumerate() {
+        raw_name[i] = c.to_ascii_uppercase();
+    }
+    for (i, c) in extension.bytes().take(3).enumerate() {
+        raw_name[8 + i] = c.to_ascii_uppercase();
+    }
+    raw_name
+}
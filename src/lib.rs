@@ -3,37 +3,89 @@
 
 #[macro_use]
 pub mod dos;
+pub mod bios;
 pub mod dpkey;
+pub mod mouse;
+pub mod psp;
+pub mod fat;
+pub mod util;
+pub mod sound;
+pub mod xms;
+pub mod ems;
+pub mod log;
 extern crate alloc;
 extern crate rlibc;
 
 use crate::dos::allocator::GLOBAL_ALLOCATOR;
-use crate::dos::cooperative_multitasking::TASKING;
 
 #[link_section = ".startup"]
 #[no_mangle]
 fn _start() -> ! {
+    dos::env::init(); // Before anything overwrites the command tail
     unsafe {
         GLOBAL_ALLOCATOR.init();
-        TASKING.init(); // Relies on the allocator
     }
     extern "Rust" {
-        fn main() -> ();
+        fn main() -> u8;
     }
-    unsafe {
-        main();
+    let return_code = unsafe { main() };
+    dos::exit(return_code);
+}
+
+/// Return types allowed for the function given to [entry], converted to the program return code.
+pub trait Termination {
+    fn report(self) -> u8;
+}
+
+impl Termination for () {
+    fn report(self) -> u8 {
+        0
+    }
+}
+
+impl Termination for u8 {
+    fn report(self) -> u8 {
+        self
+    }
+}
+
+impl<T: Termination, E: core::fmt::Debug> Termination for Result<T, E> {
+    fn report(self) -> u8 {
+        match self {
+            Ok(value) => value.report(),
+            Err(error) => {
+                println!("Error: {:?}", error);
+                1
+            }
+        }
     }
-    dos::exit(0);
 }
 
+/// Declares the function called as the program's `main`, which can return `()`, a `u8` return code
+/// or a `Result`, any of them being converted by [Termination].
+///
+/// Before the function runs, the startup code saves the PSP segment and the command tail for
+/// [dos::env], and shrinks the program's memory block and allocates the heap from DOS.
+/// [println] and a panic message go to the standard output from the start.
+/// When the function returns, the program exits with its return code (INT 21h AH=4Ch).
+///
+/// ```ignore
+/// entry!(main);
+///
+/// fn main() -> Result<(), ErrorCode> {
+///     let args: Vec<&str> = dos::env::args().collect();
+///     println!("{:?}", args);
+///     Ok(())
+/// }
+/// ```
 #[macro_export]
 macro_rules! entry {
     ($path:path) => {
         #[export_name = "main"]
-        pub fn __main() -> () {
+        pub fn __main() -> u8 {
             // type check the given path
-            let f: fn() -> () = $path;
-            f()
+            let f: fn() -> _ = $path;
+            $crate::Termination::report(f())
         }
     };
 }
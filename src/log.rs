@@ -0,0 +1,100 @@
+//! A small logger for debugging, writing timestamped lines to the console, a file or the auxiliary device.
+//!
+//! ```ignore
+//! log::set_sink(LogSink::append_to_file("PROGRAM.LOG")?);
+//! log!(Level::Info, "{} bytes free", free);
+//! ```
+//!
+//! Each line starts with the time of [Clock::now] and the level:
+//! `12:34:56 [INFO] 8192 bytes free`. Errors of the sink are ignored, so that logging never fails the program.
+//!
+//! [log!] isn't reentrant: it borrows the sink for the whole line, and calls DOS to get the time and write.
+//! It must not be used from an interrupt handler or the Ctrl-C handler, nor while a line is being written,
+//! such as from a [fmt::Display] implementation of one of its arguments.
+
+use core::fmt;
+use core::ptr::addr_of_mut;
+use crate::dos::console::Stdout;
+use crate::dos::datetime::Clock;
+use crate::dos::error_code::ErrorCode;
+use crate::dos::file::{File, OpenOptions};
+use crate::dos::output::Output;
+use crate::dos::serial::Serial;
+
+/// Logs a line at the given [Level], formatted like [print!], without the trailing CR LF.
+///
+/// ```ignore
+/// log!(Level::Warn, "retrying {}", path);
+/// ```
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        $crate::log::_log($level, format_args!($($arg)*))
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Level {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Where the lines of [log!] go.
+pub enum LogSink {
+    /// The standard output, so redirected along with it
+    Console,
+    File(File),
+    /// The auxiliary device, usually COM1, for a terminal or another machine to follow the log
+    Serial,
+}
+
+impl LogSink {
+    /// Opens a file to append to, created if it doesn't exist, so that the log of previous runs is kept.
+    pub fn append_to_file(path: &str) -> Result<Self, ErrorCode> {
+        Ok(LogSink::File(OpenOptions::new().append(true).create(true).open(path)?))
+    }
+}
+
+impl Output for LogSink {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), ErrorCode> {
+        match self {
+            LogSink::Console => Stdout.write_bytes(bytes),
+            LogSink::File(file) => file.write_bytes(bytes),
+            LogSink::Serial => Serial.write_bytes(bytes),
+        }
+    }
+}
+
+// Borrowed by _log for a whole line, which is why log! isn't reentrant
+static mut SINK: LogSink = LogSink::Console;
+
+/// Sends the following lines to `sink`, the console until then. A previous file sink is closed.
+/// Like [log!], it must not be called from an interrupt handler.
+pub fn set_sink(sink: LogSink) {
+    unsafe { *addr_of_mut!(SINK) = sink }
+}
+
+pub fn _log(level: Level, args: fmt::Arguments) {
+    let time = Clock::now().time;
+    let sink = unsafe { &mut *addr_of_mut!(SINK) };
+    let _ = write!(sink, "{:02}:{:02}:{:02} [{}] {}\r\n", time.hour, time.minute, time.second, level, args);
+}
@@ -8,7 +8,44 @@ mod interrupts;
 
 use crate::dos_tests::{
     allocator_test::allocator_test, cooperative_multitasking_test::cooperative_multitasking_test,
-    file::file_read_test,
+    file::file_read_test, memory_test::memory_test, disk_test::disk_space_test,
+    directory_test::directory_test, directory_test::current_directory_test,
+    file::file_attributes_test, exit_code_test::exit_code_test, process_test::exec_test,
+    interrupt_vector_test::interrupt_vector_test, interrupt_vector_test::interrupt_handler_test,
+    system_test::ctrl_break_test, error_code_test::extended_error_test, video_test::video_test,
+    text_screen_test::text_screen_test, keyboard_test::keyboard_test, mouse_test::mouse_test,
+    timer_test::timer_test, cooperative_multitasking_test::task_test, panic_test::panic_test,
+    process_test::exec_panic_test, env_test::args_test, psp_test::psp_test,
+    env_test::environment_test, disk_test::drive_letter_test, fcb_test::parse_filename_test,
+    file::file_modified_test, file::file_lock_test, file::stdio_redirect_test,
+    file::handle_count_test, memory_test::allocation_strategy_test, country_test::country_info_test,
+    tsr_test::tsr_test, system_test::in_dos_flag_test, ioctl_test::device_info_test,
+    directory_test::canonicalize_test, fcb_test::random_block_read_test,
+    console_test::direct_console_write_test, console_test::direct_console_read_test,
+    console_test::has_input_waiting_test, country_test::code_page_test,
+    country_test::country_tables_test, file::file_commit_test, file::file_create_new_test,
+    file::file_create_temp_test, file::buf_reader_test, file::buf_writer_test,
+    system_test::switch_char_test, system_test::verify_test, serial_test::serial_loopback_test,
+    bios_serial_test::bios_serial_test, printer_test::printer_test,
+    bios_disk_test::boot_sector_test, disk_test::absolute_sector_test, fat_test::fat_read_file_test,
+    sysvars_test::list_of_lists_test, memory_test::memory_blocks_test,
+    system_test::wait_for_external_event_test, country_test::dbcs_test, file::flush_all_files_test,
+    fcb_test::fcb_open_failure_test, system_test::ctrl_c_handler_test, text_test::attribute_test,
+    util_test::array_string_test, critical_error_test::critical_error_handler_test,
+    datetime_test::dos_word_test, disk_test::disk_parameter_block_test,
+    fcb_test::fcb_random_read_test, console_test::stdin_test, file::file_size_test,
+    file::rename_remove_test, file::metadata_test, file::copy_test,
+    exit_code_test::entry_setup_test, keyboard_test::peek_key_test,
+    text_screen_test::screen_flush_test, video_test::mode_13h_test, timer_test::sleep_test,
+    rtc_test::rtc_test, sound_test::sound_test, io_test::port_read_test, xms_test::xms_test,
+    ems_test::ems_test, file::write_all_read_exact_test, disk_test::logical_drive_count_test,
+    disk_test::drives_test, disk_test::allocation_info_test, console_test::print_at_test,
+    interrupt_vector_test::critical_section_test, file::read_stdin_to_end_test,
+    disk_test::media_id_test, disk_test::volume_label_test, error_code_test::with_retry_test,
+    error_code_test::with_retry_removable_test, datetime_test::clock_test, psp_test::psp_guard_test,
+    file::stdin_redirect_test, file::read_whole_file_test, file::write_whole_file_test,
+    file::open_options_test, keyboard_test::translate_test, output_test::output_test,
+    output_test::text_screen_output_test, log_test::log_test, system_test::boot_drive_test,
 };
 use interrupts::display_string;
 use rust_dos::*;
@@ -16,11 +53,115 @@ use rust_dos::*;
 //use crate::dos_tests::file::file_read_test;
 
 entry!(main);
+//entry!(exit_code_test);
+//entry!(panic_test);
+//entry!(entry_setup_test);
 
 fn main() {
     //allocator_test();
     //file_read_test();
     //cooperative_multitasking_test();
+    //memory_test();
+    //disk_space_test();
+    //directory_test();
+    //current_directory_test();
+    //file_attributes_test();
+    //exec_test();
+    //interrupt_vector_test();
+    //interrupt_handler_test();
+    //ctrl_break_test();
+    //extended_error_test();
+    //video_test();
+    //text_screen_test();
+    //keyboard_test();
+    //mouse_test();
+    //timer_test();
+    //task_test();
+    //panic_test();
+    //exec_panic_test();
+    //args_test();
+    //psp_test();
+    //environment_test();
+    //drive_letter_test();
+    //parse_filename_test();
+    //file_modified_test();
+    //file_lock_test();
+    //stdio_redirect_test();
+    //handle_count_test();
+    //allocation_strategy_test();
+    //country_info_test();
+    //tsr_test();
+    //in_dos_flag_test();
+    //device_info_test();
+    //canonicalize_test();
+    //random_block_read_test();
+    //direct_console_write_test();
+    //direct_console_read_test();
+    //has_input_waiting_test();
+    //code_page_test();
+    //country_tables_test();
+    //file_commit_test();
+    //file_create_new_test();
+    //file_create_temp_test();
+    //buf_reader_test();
+    //buf_writer_test();
+    //switch_char_test();
+    //verify_test();
+    //serial_loopback_test();
+    //bios_serial_test();
+    //printer_test();
+    //boot_sector_test();
+    //absolute_sector_test();
+    //fat_read_file_test();
+    //list_of_lists_test();
+    //memory_blocks_test();
+    //wait_for_external_event_test();
+    //dbcs_test();
+    //flush_all_files_test();
+    //fcb_open_failure_test();
+    //ctrl_c_handler_test();
+    //attribute_test();
+    //array_string_test();
+    //critical_error_handler_test();
+    //dos_word_test();
+    //disk_parameter_block_test();
+    //fcb_random_read_test();
+    //stdin_test();
+    //file_size_test();
+    //rename_remove_test();
+    //metadata_test();
+    //copy_test();
+    //peek_key_test();
+    //screen_flush_test();
+    //mode_13h_test();
+    //sleep_test();
+    //rtc_test();
+    //sound_test();
+    //port_read_test();
+    //xms_test();
+    //ems_test();
+    //write_all_read_exact_test();
+    //logical_drive_count_test();
+    //drives_test();
+    //allocation_info_test();
+    //print_at_test();
+    //critical_section_test();
+    //read_stdin_to_end_test();
+    //media_id_test();
+    //volume_label_test();
+    //with_retry_test();
+    //with_retry_removable_test();
+    //clock_test();
+    //psp_guard_test();
+    //stdin_redirect_test();
+    //read_whole_file_test();
+    //write_whole_file_test();
+    //open_options_test();
+    //translate_test();
+    //output_test();
+    //text_screen_output_test();
+    //log_test();
+    //boot_drive_test();
 
     display_string("Hello, world!$");
 }
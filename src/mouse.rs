@@ -0,0 +1,106 @@
+//! Mouse driver services (INT 33h). A mouse driver such as MOUSE.COM must be loaded.
+//!
+//! Coordinates are in virtual screen pixels: in graphics modes they match the screen pixels,
+//! except in the 320 pixels wide modes 4, 5 (and 13h) where the horizontal coordinate is doubled, ranging from 0 to 639.
+//! In text modes, each character cell is 8x8 virtual pixels, so divide by 8 to get the column and row.
+
+use core::arch::asm;
+use core::ops::BitOr;
+use crate::dos::interrupt_vector::get_interrupt_vector;
+
+/// Buttons currently pressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseButtons(u8);
+
+impl MouseButtons {
+    pub const NONE: MouseButtons = MouseButtons(0x00);
+    pub const LEFT: MouseButtons = MouseButtons(0x01);
+    pub const RIGHT: MouseButtons = MouseButtons(0x02);
+    pub const MIDDLE: MouseButtons = MouseButtons(0x04);
+
+    pub const fn from_bits(bits: u8) -> Self {
+        MouseButtons(bits)
+    }
+
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn contains(&self, other: MouseButtons) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for MouseButtons {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        MouseButtons(self.0 | rhs.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseStatus {
+    pub x: u16,
+    pub y: u16,
+    pub buttons: MouseButtons,
+}
+
+/// Resets the mouse driver, hiding the cursor and moving it to the center of the screen (INT 33h AX=0000h).
+/// Returns the number of buttons (0 if the driver doesn't tell), or None if no mouse driver is installed.
+pub fn reset() -> Option<u8> {
+    // without a driver, the vector can be null on older DOS versions
+    if get_interrupt_vector(0x33).is_null() {
+        return None;
+    }
+    let status: u16;
+    let buttons: u16;
+    unsafe { asm!("int 0x33", inout("ax") 0x0000_u16 => status, out("bx") buttons) }
+    if status != 0xFFFF {
+        return None;
+    }
+    match buttons {
+        0xFFFF => Some(2),
+        _ => Some(buttons as u8),
+    }
+}
+
+/// Shows the mouse cursor (INT 33h AX=0001h).
+/// The driver keeps a counter: it takes as many calls to show as there were to [hide].
+pub fn show() {
+    unsafe { asm!("int 0x33", inout("ax") 0x0001_u16 => _) }
+}
+
+/// Hides the mouse cursor (INT 33h AX=0002h).
+pub fn hide() {
+    unsafe { asm!("int 0x33", inout("ax") 0x0002_u16 => _) }
+}
+
+/// Gets the position and buttons of the mouse (INT 33h AX=0003h).
+pub fn get_status() -> MouseStatus {
+    let buttons: u16;
+    let x: u16;
+    let y: u16;
+    unsafe {
+        asm!("int 0x33",
+             inout("ax") 0x0003_u16 => _,
+             out("bx") buttons,
+             out("cx") x,
+             out("dx") y);
+    }
+    MouseStatus {
+        x,
+        y,
+        buttons: MouseButtons::from_bits(buttons as u8),
+    }
+}
+
+/// Moves the mouse cursor (INT 33h AX=0004h).
+pub fn set_position(x: u16, y: u16) {
+    unsafe {
+        asm!("int 0x33",
+             inout("ax") 0x0004_u16 => _,
+             in("cx") x,
+             in("dx") y);
+    }
+}
@@ -0,0 +1,100 @@
+//! Program Segment Prefix, the 256 bytes DOS puts before a program in memory.
+
+use core::arch::asm;
+use core::ptr::read_volatile;
+use crate::dos::far_ptr::FarPtr;
+
+/// The PSP of a program, identified by its segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Psp {
+    segment: u16,
+}
+
+impl Psp {
+    const TERMINATE_ADDRESS_OFFSET: u16 = 0x0A;
+    const PARENT_PSP_OFFSET: u16 = 0x16;
+    const ENVIRONMENT_SEGMENT_OFFSET: u16 = 0x2C;
+    pub const FIRST_FCB_OFFSET: u16 = 0x5C;
+    pub const SECOND_FCB_OFFSET: u16 = 0x6C;
+    const COMMAND_TAIL_OFFSET: u16 = 0x80;
+
+    pub const fn new(segment: u16) -> Self {
+        Psp { segment }
+    }
+
+    /// PSP of the running program (INT 21h AH=51h).
+    pub fn current() -> Self {
+        Psp::new(get_current_psp())
+    }
+
+    pub fn segment(&self) -> u16 {
+        self.segment
+    }
+
+    fn read<T>(&self, offset: u16) -> T {
+        unsafe { read_volatile(FarPtr::new(self.segment, offset).as_ptr::<T>()) }
+    }
+
+    /// Segment of the environment block, a list of `NAME=VALUE` ASCIIZ strings.
+    pub fn environment_segment(&self) -> u16 {
+        self.read(Self::ENVIRONMENT_SEGMENT_OFFSET)
+    }
+
+    /// Arguments given to the program, as typed, usually starting with a space.
+    /// The default DTA shares this memory, so DOS overwrites it on calls such as find first.
+    pub fn command_tail(&self) -> &[u8] {
+        let len: u8 = self.read(Self::COMMAND_TAIL_OFFSET);
+        let tail = FarPtr::new(self.segment, Self::COMMAND_TAIL_OFFSET + 1).as_ptr::<u8>();
+        unsafe { core::slice::from_raw_parts(tail, (len as usize).min(127)) }
+    }
+
+    /// Address DOS returns to when the program terminates, in the parent program.
+    pub fn terminate_address(&self) -> FarPtr {
+        FarPtr::new(self.read(Self::TERMINATE_ADDRESS_OFFSET + 2), self.read(Self::TERMINATE_ADDRESS_OFFSET))
+    }
+
+    /// Segment of the PSP of the parent program. COMMAND.COM is its own parent.
+    pub fn parent_psp(&self) -> u16 {
+        self.read(Self::PARENT_PSP_OFFSET)
+    }
+}
+
+/// Gets the segment of the PSP DOS considers running (INT 21h AH=51h).
+/// In a TSR called from an interrupt, it is the one of the interrupted program.
+pub fn get_current_psp() -> u16 {
+    let segment: u16;
+    unsafe { asm!("mov ah, 0x51", "int 0x21", out("ax") _, out("bx") segment) }
+    segment
+}
+
+/// Sets the PSP DOS considers running (INT 21h AH=50h): open files, the DTA and memory allocations are
+/// those of this PSP, and terminating returns to its parent.
+/// Prefer [PspGuard], which restores it.
+pub fn set_current_psp(segment: u16) {
+    unsafe { asm!("int 0x21", in("ah") 0x50_u8, in("bx") segment) }
+}
+
+/// Switches the current PSP until dropped, so that a TSR uses its own files instead of the interrupted program's.
+/// Like any DOS call from an interrupt handler, it must only be used when DOS isn't busy.
+///
+/// ```ignore
+/// let _guard = PspGuard::new(TSR_PSP);
+/// let file = File::open("C:\\TSR.LOG");
+/// ```
+pub struct PspGuard {
+    saved_segment: u16,
+}
+
+impl PspGuard {
+    pub fn new(segment: u16) -> Self {
+        let saved_segment = get_current_psp();
+        set_current_psp(segment);
+        PspGuard { saved_segment }
+    }
+}
+
+impl Drop for PspGuard {
+    fn drop(&mut self) {
+        set_current_psp(self.saved_segment);
+    }
+}
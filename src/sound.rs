@@ -0,0 +1,43 @@
+//! PC speaker tones, from channel 2 of the 8253/8254 timer, gated through port 61h.
+
+use crate::bios::timer::sleep;
+use crate::dos::io::{inb, outb};
+
+/// Input frequency of the timer, in Hz.
+pub const TIMER_FREQUENCY: u32 = 1193180;
+
+const TIMER_CHANNEL_2_PORT: u16 = 0x42;
+const TIMER_COMMAND_PORT: u16 = 0x43;
+/// Channel 2, low then high byte of the divisor, mode 3 (square wave), binary
+const CHANNEL_2_SQUARE_WAVE: u8 = 0xB6;
+const SPEAKER_PORT: u16 = 0x61;
+/// Bit 0 gates the timer to the speaker, bit 1 enables the speaker
+const SPEAKER_BITS: u8 = 0x03;
+
+/// Plays a tone until [silence] is called. Frequencies below 19 Hz are played at 19 Hz, and 0 silences the speaker.
+pub fn tone(frequency_hz: u16) {
+    if frequency_hz == 0 {
+        silence();
+        return;
+    }
+    // The divisor is 16 bits
+    let divisor = (TIMER_FREQUENCY / frequency_hz as u32).min(0xFFFF) as u16;
+    unsafe {
+        outb(TIMER_COMMAND_PORT, CHANNEL_2_SQUARE_WAVE);
+        outb(TIMER_CHANNEL_2_PORT, divisor as u8);
+        outb(TIMER_CHANNEL_2_PORT, (divisor >> 8) as u8);
+        outb(SPEAKER_PORT, inb(SPEAKER_PORT) | SPEAKER_BITS);
+    }
+}
+
+/// Stops the tone played by [tone].
+pub fn silence() {
+    unsafe { outb(SPEAKER_PORT, inb(SPEAKER_PORT) & !SPEAKER_BITS) }
+}
+
+/// Plays a tone for a number of timer ticks of about 55 ms, busy-waiting.
+pub fn beep(frequency_hz: u16, duration_ticks: u32) {
+    tone(frequency_hz);
+    sleep(duration_ticks);
+    silence();
+}
@@ -0,0 +1,107 @@
+//! Helpers that don't call DOS or the BIOS.
+
+use core::fmt;
+
+/// Returned when a string doesn't fit in an [ArrayString].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError;
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "String too long")
+    }
+}
+
+/// A string of at most `N - 1` bytes, stored inline and always followed by a NUL,
+/// to build the ASCIIZ strings DOS functions take without allocating.
+/// On the stack, it is also in our data segment, as DS:DX buffers must be.
+///
+/// Characters are stored as single bytes, so only ASCII (or code page) characters can be pushed.
+#[derive(Clone)]
+pub struct ArrayString<const N: usize> {
+    bytes: [u8; N],
+    length: usize,
+}
+
+impl<const N: usize> ArrayString<N> {
+    const HAS_TERMINATOR: () = assert!(N > 0, "ArrayString needs room for its terminator");
+
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::HAS_TERMINATOR;
+        ArrayString { bytes: [0; N], length: 0 }
+    }
+
+    /// Maximum length, without the terminator.
+    pub const fn capacity(&self) -> usize {
+        N - 1
+    }
+
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Appends a string, or nothing at all if it doesn't fit.
+    pub fn push_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        let new_length = self.length + s.len();
+        if new_length > self.capacity() {
+            return Err(CapacityError);
+        }
+        self.bytes[self.length..new_length].copy_from_slice(s.as_bytes());
+        self.bytes[new_length] = 0;
+        self.length = new_length;
+        Ok(())
+    }
+
+    /// Appends a character, truncated to a byte.
+    pub fn push(&mut self, c: char) -> Result<(), CapacityError> {
+        if self.length == self.capacity() {
+            return Err(CapacityError);
+        }
+        self.bytes[self.length] = c as u8;
+        self.length += 1;
+        self.bytes[self.length] = 0;
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.length = 0;
+        self.bytes[0] = 0;
+    }
+
+    /// The bytes of the string, without the terminator.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.length]
+    }
+
+    /// Pointer to the NUL-terminated string, valid as long as the string isn't modified or moved.
+    pub fn as_c_str(&self) -> *const u8 {
+        self.bytes.as_ptr()
+    }
+}
+
+impl<const N: usize> Default for ArrayString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Write for ArrayString<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const N: usize> fmt::Debug for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"")?;
+        for &c in self.as_bytes() {
+            write!(f, "{}", (c as char).escape_debug())?;
+        }
+        write!(f, "\"")
+    }
+}
@@ -0,0 +1,240 @@
+//! Extended memory above 1 MB, through an XMS driver such as HIMEM.SYS.
+//!
+//! The driver is found with INT 2Fh, and then called through its entry point with a far call.
+//! Extended memory isn't freed by DOS when the program terminates: [XmsHandle]s are freed when dropped,
+//! but a program killed by a critical error or Ctrl-C leaks them until the next reboot.
+
+use core::arch::asm;
+use core::fmt;
+use core::ptr::{addr_of, addr_of_mut};
+use crate::dos::far_ptr::FarPtr;
+
+/// Error code returned by the driver in BL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XmsError {
+    /// No XMS driver is loaded
+    NotInstalled,
+    NotImplemented,
+    VdiskDetected,
+    A20Error,
+    GeneralDriverError,
+    UnrecoverableDriverError,
+    /// All the extended memory is allocated
+    OutOfMemory,
+    /// All the handles are used
+    OutOfHandles,
+    InvalidHandle,
+    InvalidSourceHandle,
+    InvalidSourceOffset,
+    InvalidDestinationHandle,
+    InvalidDestinationOffset,
+    /// Lengths must be even
+    InvalidLength,
+    InvalidOverlap,
+    ParityError,
+    BlockLocked,
+    Other(u8),
+}
+
+impl From<u8> for XmsError {
+    fn from(code: u8) -> Self {
+        match code {
+            0x80 => XmsError::NotImplemented,
+            0x81 => XmsError::VdiskDetected,
+            0x82 => XmsError::A20Error,
+            0x8E => XmsError::GeneralDriverError,
+            0x8F => XmsError::UnrecoverableDriverError,
+            0xA0 => XmsError::OutOfMemory,
+            0xA1 => XmsError::OutOfHandles,
+            0xA2 => XmsError::InvalidHandle,
+            0xA3 => XmsError::InvalidSourceHandle,
+            0xA4 => XmsError::InvalidSourceOffset,
+            0xA5 => XmsError::InvalidDestinationHandle,
+            0xA6 => XmsError::InvalidDestinationOffset,
+            0xA7 => XmsError::InvalidLength,
+            0xA8 => XmsError::InvalidOverlap,
+            0xA9 => XmsError::ParityError,
+            0xAB => XmsError::BlockLocked,
+            code => XmsError::Other(code),
+        }
+    }
+}
+
+impl fmt::Display for XmsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            XmsError::NotInstalled => write!(f, "No XMS driver"),
+            XmsError::OutOfMemory => write!(f, "Out of extended memory"),
+            XmsError::OutOfHandles => write!(f, "Out of XMS handles"),
+            XmsError::Other(code) => write!(f, "XMS error {:02X}h", code),
+            error => write!(f, "{:?}", error),
+        }
+    }
+}
+
+/// Entry point of the driver, offset in the low word and segment in the high word, as a far call takes it.
+static mut ENTRY_POINT: u32 = 0;
+
+/// Returns true if an XMS driver is loaded (INT 2Fh AX=4300h).
+pub fn is_installed() -> bool {
+    let status: u16;
+    unsafe { asm!("int 0x2f", inout("ax") 0x4300_u16 => status) }
+    status as u8 == 0x80
+}
+
+/// Gets the entry point of the driver (INT 2Fh AX=4310h), once.
+fn entry_point() -> Result<u32, XmsError> {
+    if unsafe { addr_of!(ENTRY_POINT).read() } == 0 {
+        if !is_installed() {
+            return Err(XmsError::NotInstalled);
+        }
+        let segment: u16;
+        let offset: u16;
+        unsafe {
+            asm!("push es", "int 0x2f", "mov {segment:x}, es", "pop es",
+                 segment = out(reg) segment,
+                 inout("ax") 0x4310_u16 => _,
+                 out("bx") offset);
+            addr_of_mut!(ENTRY_POINT).write((segment as u32) << 16 | offset as u32);
+        }
+    }
+    Ok(unsafe { addr_of!(ENTRY_POINT).read() })
+}
+
+/// Calls a driver function with AH, DX and SI, and returns AX, BX and DX.
+fn xms_call(function: u8, dx: u16, si: u16) -> Result<(u16, u16, u16), XmsError> {
+    entry_point()?;
+    let ax: u16;
+    let bx: u16;
+    let dx_out: u16;
+    unsafe {
+        // A far call through the entry point, not an interrupt
+        asm!("push si", "mov si, {si:x}", "lcall [{entry_point}]", "pop si",
+             si = in(reg) si,
+             entry_point = sym ENTRY_POINT,
+             inout("ax") (function as u16) << 8 => ax,
+             out("bx") bx,
+             inout("dx") dx => dx_out);
+    }
+    Ok((ax, bx, dx_out))
+}
+
+/// Calls a driver function returning AX=1 on success, and the error code in BL on failure.
+fn xms_function(function: u8, dx: u16, si: u16) -> Result<(u16, u16), XmsError> {
+    let (ax, bx, dx) = xms_call(function, dx, si)?;
+    if ax != 1 {
+        return Err(XmsError::from(bx as u8));
+    }
+    Ok((bx, dx))
+}
+
+/// Gets the free extended memory, as (largest block, total), in KB (XMS function 08h).
+pub fn query_free() -> Result<(u16, u16), XmsError> {
+    let (largest, bx, total) = xms_call(0x08, 0, 0)?;
+    // BL is 0 on success, some drivers leave it unchanged
+    if largest == 0 && bx as u8 >= 0x80 {
+        return Err(XmsError::from(bx as u8));
+    }
+    Ok((largest, total))
+}
+
+/// Allocates a block of extended memory (XMS function 09h).
+pub fn allocate(kb: u16) -> Result<XmsHandle, XmsError> {
+    let (_, handle) = xms_function(0x09, kb, 0)?;
+    Ok(XmsHandle { handle, kb })
+}
+
+/// A memory location for [move_block].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XmsAddress {
+    /// Memory below 1 MB
+    Conventional(FarPtr),
+    /// An offset in a block of extended memory
+    Extended { handle: u16, offset: u32 },
+}
+
+impl XmsAddress {
+    /// As stored in the move structure: handle 0 means that the offset is a segment:offset pointer.
+    fn to_handle_and_offset(self) -> (u16, u32) {
+        match self {
+            XmsAddress::Conventional(ptr) => (0, (ptr.segment as u32) << 16 | ptr.offset as u32),
+            XmsAddress::Extended { handle, offset } => (handle, offset),
+        }
+    }
+}
+
+/// Parameters of XMS function 0Bh.
+#[repr(C, packed)]
+struct ExtendedMemoryMove {
+    length: u32,
+    source_handle: u16,
+    source_offset: u32,
+    destination_handle: u16,
+    destination_offset: u32,
+}
+
+/// Copies `length` bytes, which must be even, between conventional and extended memory (XMS function 0Bh).
+///
+/// # Safety
+/// Conventional memory locations must be valid for `length` bytes.
+pub unsafe fn move_block(length: u32, source: XmsAddress, destination: XmsAddress) -> Result<(), XmsError> {
+    let (source_handle, source_offset) = source.to_handle_and_offset();
+    let (destination_handle, destination_offset) = destination.to_handle_and_offset();
+    let parameters = ExtendedMemoryMove { // To be sure of the segment
+        length,
+        source_handle,
+        source_offset,
+        destination_handle,
+        destination_offset,
+    };
+    xms_function(0x0B, 0, &parameters as *const ExtendedMemoryMove as u16)?;
+    Ok(())
+}
+
+/// A block of extended memory, freed when dropped.
+#[derive(Debug)]
+pub struct XmsHandle {
+    handle: u16,
+    kb: u16,
+}
+
+impl XmsHandle {
+    pub fn handle(&self) -> u16 {
+        self.handle
+    }
+
+    /// Size of the block, in KB.
+    pub fn kb(&self) -> u16 {
+        self.kb
+    }
+
+    /// Copies `data`, whose length must be even, to `offset` in the block.
+    pub fn write(&self, offset: u32, data: &[u8]) -> Result<(), XmsError> {
+        let destination = XmsAddress::Extended { handle: self.handle, offset };
+        unsafe { move_block(data.len() as u32, XmsAddress::Conventional(FarPtr::from_ptr(data.as_ptr())), destination) }
+    }
+
+    /// Fills `buffer`, whose length must be even, from `offset` in the block.
+    pub fn read(&self, offset: u32, buffer: &mut [u8]) -> Result<(), XmsError> {
+        let source = XmsAddress::Extended { handle: self.handle, offset };
+        unsafe { move_block(buffer.len() as u32, source, XmsAddress::Conventional(FarPtr::from_ptr(buffer.as_ptr()))) }
+    }
+
+    /// Frees the block (XMS function 0Ah).
+    pub fn free(self) -> Result<(), XmsError> {
+        let result = self.free_with_ref();
+        core::mem::forget(self);
+        result
+    }
+
+    fn free_with_ref(&self) -> Result<(), XmsError> {
+        xms_function(0x0A, self.handle, 0)?;
+        Ok(())
+    }
+}
+
+impl Drop for XmsHandle {
+    fn drop(&mut self) {
+        let _ = self.free_with_ref();
+    }
+}